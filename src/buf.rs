@@ -0,0 +1,97 @@
+//! Module with a fixed-capacity buffer that can be used as the nested writer of
+//! [`Writer`](crate::latex_writer::Writer) when there is no allocator, e.g. on embedded targets.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     buf::FixedCapacityBuffer,
+//!     fmt::labels::{Counters, CountersChange, Label, LabelGenerator},
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::DisplayMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! let mut w = Writer::<AmsLatex, NoFeatures, DisplayMathMode, FixedCapacityBuffer<32>>::default();
+//! let mut counters = Counters::new();
+//! let label = unsafe { counters.write_next_label(&mut w, CountersChange::IncrementEquationAndAddSubeq) }
+//!     .unwrap();
+//! let mut name = FixedCapacityBuffer::<8>::new();
+//! label.write_name(&mut name).unwrap();
+//! assert_eq!(name.as_str(), "1a");
+//!
+//! let (buf, _no_features) = w.into_raw_parts();
+//! assert_eq!(buf.as_str(), r"\tag{1a}\label{1a}");
+//!
+//! // Writing more than the capacity fails instead of allocating
+//! let mut tiny = FixedCapacityBuffer::<2>::new();
+//! assert!(core::fmt::Write::write_str(&mut tiny, "abc").is_err());
+//! assert_eq!(tiny.as_str(), "");
+//! ```
+
+use core::fmt::{Error, Write};
+
+/// A string buffer with the capacity of `N` bytes that is stored inline.
+///
+/// Writing a string that does not fit fails with [`core::fmt::Error`] and leaves the
+/// buffer unchanged.
+#[derive(Clone, Copy)]
+pub struct FixedCapacityBuffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedCapacityBuffer<N> {
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole strings are ever copied into the buffer
+        unsafe { core::str::from_utf8_unchecked(&self.bytes[..self.len]) }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for FixedCapacityBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Write for FixedCapacityBuffer<N> {
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        let end = self.len.checked_add(s.len()).ok_or(Error)?;
+        if end > N {
+            return Err(Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for FixedCapacityBuffer<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
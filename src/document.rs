@@ -0,0 +1,188 @@
+//! Module with a builder of standalone [LaTeX] documents.
+//!
+//! The body of the document is written with a [`Writer`] in [`VerticalMode`], which is
+//! the mode [LaTeX] is in between paragraphs. Paragraphs are started with
+//! [`Writer::begin_paragraph`] and must be ended with [`Writer::end_paragraph`] before
+//! the document can be finished, so a document with an unclosed paragraph or an unclosed
+//! math block does not compile.
+//!
+//! Since the required packages are known only after the body is written, the body is
+//! buffered and the preamble with the exact list of `\usepackage` commands is written
+//! when the document is finished. The body writer also records the written labels and
+//! references to them, which are checked with [`ReferenceRegistry::report`] when the
+//! document is finished, so that a document with references rendered as `??` can be caught.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra::matrix;
+//! use nalgebra_latex::{
+//!     document::Document,
+//!     fmt::LatexFormatter,
+//!     matrix::fmt::ParenthesizedMatrixFormatter,
+//!     text::WriteText,
+//! };
+//!
+//! let (doc, body) = Document::new(String::new(), "article").begin_document();
+//! let mut paragraph = body.begin_paragraph();
+//! paragraph.write_text("Consider ").unwrap();
+//! let mut paragraph = paragraph
+//!     .write_inline_math(|w| ParenthesizedMatrixFormatter::fmt(w, &matrix![1, 2; 3, 4]))
+//!     .unwrap();
+//! paragraph.write_text(".").unwrap();
+//! let body = paragraph.end_paragraph().unwrap();
+//! let (s, report) = doc.end_document(body).unwrap();
+//! assert!(report.is_clean());
+//!
+//! assert_eq!(
+//!     s,
+//!     concat!(
+//!         "\\documentclass{article}\n",
+//!         "\\usepackage{amsmath}\n",
+//!         "\\begin{document}\n",
+//!         "Consider $\\begin{pmatrix}1&2\\\\3&4\\end{pmatrix}$.\n\n",
+//!         "\\end{document}\n",
+//!     )
+//! );
+//! ```
+//!
+//! A reference to an equation that is not labelled in the body is reported:
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     document::Document,
+//!     fmt::{
+//!         labels::{Counters, CountersChange, LabelGenerator},
+//!         WriteAsLatex,
+//!     },
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::{DisplayMathMode, OuterParagraphMode},
+//!     latex_writer::{LatexWriter, Writer},
+//!     text::WriteText,
+//! };
+//!
+//! // The label is written somewhere else
+//! let mut scratch = Writer::<AmsLatex, NoFeatures, DisplayMathMode, String>::default();
+//! let mut counters = Counters::new();
+//! let label = unsafe { counters.write_next_label(&mut scratch, CountersChange::IncrementEquation) }
+//!     .unwrap();
+//!
+//! let (doc, body) = Document::new(String::new(), "article").begin_document();
+//! let mut paragraph = body.begin_paragraph();
+//! paragraph.write_text("See ").unwrap();
+//! let paragraph: Writer<_, _, OuterParagraphMode, _> = label.write_as_latex(paragraph).unwrap();
+//! let body = paragraph.end_paragraph().unwrap();
+//! let (_s, report) = doc.end_document(body).unwrap();
+//! assert!(!report.is_ok());
+//! assert_eq!(report.dangling_references(), ["1"]);
+//! ```
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+
+use alloc::string::String;
+use core::{fmt::Error, marker::PhantomData};
+
+use crate::{
+    fmt::references::{ReferenceRegistry, ReferenceReport},
+    latex_features::{Package, RequiredPackages, WithReferenceRegistry},
+    latex_flavors::AmsLatex,
+    latex_modes::VerticalMode,
+    latex_writer::{LatexWriter, Writer},
+};
+
+/// The state of a [`Document`] before `\begin{document}`
+pub struct Preamble;
+
+/// The state of a [`Document`] after `\begin{document}`
+pub struct Body;
+
+/// The writer of the body of a [`Document`]
+pub type BodyWriter =
+    Writer<AmsLatex, WithReferenceRegistry<RequiredPackages>, VerticalMode, String>;
+
+/// A standalone [LaTeX] document.
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+pub struct Document<'a, W, S> {
+    dest: W,
+    class: &'a str,
+    class_options: Option<&'a str>,
+    packages: RequiredPackages,
+    state: PhantomData<S>,
+}
+
+impl<'a, W> Document<'a, W, Preamble>
+where
+    W: core::fmt::Write,
+{
+    /// Creates a document of the given class, e.g. `article`, which will be written to `dest`
+    pub fn new(dest: W, class: &'a str) -> Self {
+        Self {
+            dest,
+            class,
+            class_options: None,
+            packages: RequiredPackages::new(),
+            state: PhantomData,
+        }
+    }
+
+    /// Sets the options of the document class, e.g. `12pt,a4paper`
+    pub fn class_options(mut self, options: &'a str) -> Self {
+        self.class_options = Some(options);
+        self
+    }
+
+    /// Adds the package to the preamble even if nothing in the body requires it
+    pub fn require_package(mut self, package: Package) -> Self {
+        self.packages.insert(package);
+        self
+    }
+
+    /// Begins the body of the document and returns the writer for it
+    pub fn begin_document(self) -> (Document<'a, W, Body>, BodyWriter) {
+        let doc = Document {
+            dest: self.dest,
+            class: self.class,
+            class_options: self.class_options,
+            packages: self.packages,
+            state: PhantomData,
+        };
+        let features = WithReferenceRegistry::new(RequiredPackages::new());
+        let body = unsafe { BodyWriter::from_raw_parts(String::new(), features) };
+        (doc, body)
+    }
+}
+
+impl<'a, W> Document<'a, W, Body>
+where
+    W: core::fmt::Write,
+{
+    /// Writes the whole document, including the preamble with the packages required by the body,
+    /// and returns the destination with the [`ReferenceReport`] of the labels and references
+    /// written in the body.
+    pub fn end_document(mut self, body: BodyWriter) -> Result<(W, ReferenceReport), Error> {
+        let (body, features) = body.into_raw_parts();
+        let (required_packages, registry): (_, ReferenceRegistry) = features.into_parts();
+        let mut packages = self.packages;
+        for package in required_packages.iter() {
+            packages.insert(package);
+        }
+
+        let dest = &mut self.dest;
+        dest.write_str(r"\documentclass")?;
+        if let Some(options) = self.class_options {
+            dest.write_char('[')?;
+            dest.write_str(options)?;
+            dest.write_char(']')?;
+        }
+        dest.write_char('{')?;
+        dest.write_str(self.class)?;
+        dest.write_str("}\n")?;
+        packages.write_usepackages(dest)?;
+        dest.write_str("\\begin{document}\n")?;
+        dest.write_str(&body)?;
+        dest.write_str("\\end{document}\n")?;
+        Ok((self.dest, registry.report()))
+    }
+}
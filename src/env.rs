@@ -1,8 +1,119 @@
+//! Module with [LaTeX] environments, i.e. the blocks delimited with `\begin{name}` and `\end{name}`.
+//!
+//! [`LatexEnvironment`] describes an environment at the type level: its name, the package
+//! that provides it and the mode inside of it. The modes from which an environment can
+//! be entered are expressed with the bounds of the implementations, e.g. [`AlignEnvironment`]
+//! can be entered only from a [paragraph mode](crate::latex_modes::ParagraphLatexMode),
+//! whereas [`AlignedEnvironment`] can be entered only from a math mode.
+//!
+//! Environments with arguments, e.g. [`ArrayEnvironment`], are values that implement
+//! the companion trait [`LatexEnvironmentWithArgs`].
+//! Starred variants, e.g. `align*`, are given by [`Starred`].
+//!
+//! [`LatexEnvironment::with_env`] and [`LatexEnvironmentWithArgs::with_env_with_args`] write
+//! the body of the environment with a closure between the opening and the matching closing tag,
+//! so that nested environments are always closed in the right order.
+//! The lower-level pairs of `write_opening_tag` and `write_closing_tag` are `unsafe`
+//! because nothing checks that they match.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     env::{
+//!         AlignEnvironment, AlignedEnvironment, ArrayEnvironment, ArrayPosition,
+//!         LatexEnvironment, LatexEnvironmentWithArgs, Starred,
+//!     },
+//!     error::Error,
+//!     latex_features::{NoFeatures, Package},
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::InnerParagraphMode,
+//!     latex_writer::{LatexWriter, Writer},
+//!     text::{RawLatex, WriteRawLatex},
+//! };
+//!
+//! type W = Writer<AmsLatex, NoFeatures, InnerParagraphMode, String>;
+//!
+//! let w = W::default().track_packages();
+//! let w = <Starred<AlignEnvironment>>::with_env(w, |mut w| {
+//!     w.write_raw(RawLatex::new(r"x&=").unwrap())?;
+//!     let array =
+//!         ArrayEnvironment::new(RawLatex::new("cc").unwrap()).with_position(ArrayPosition::Top);
+//!     let w = array.with_env_with_args(w, |mut w| {
+//!         w.write_raw(RawLatex::new(r"1&2").unwrap())?;
+//!         Ok::<_, Error>(w)
+//!     })?;
+//!     AlignedEnvironment::with_env(w, Ok::<_, Error>)
+//! })
+//! .unwrap();
+//! assert!(w.required_packages().contains(Package::Amsmath));
+//! let (s, _required_packages) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{align*}x&=\begin{array}[t]{cc}1&2\end{array}\begin{aligned}\end{aligned}\end{align*}"
+//! );
+//! ```
+//!
+//! The following does not compile because `align` cannot be nested in display math:
+//!
+//! ```compile_fail
+//! use nalgebra_latex::{
+//!     env::{AlignEnvironment, LatexEnvironment},
+//!     error::Error,
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::DisplayMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! let w = Writer::<AmsLatex, NoFeatures, DisplayMathMode, String>::default();
+//! let w = AlignEnvironment::with_env(w, Ok::<_, Error>).unwrap();
+//! ```
+//!
+//! Since `align` and `aligned` are both in display math, their modes do not tell them apart
+//! and only [`LatexEnvironment::with_env`] keeps `aligned` from being left unclosed inside
+//! of `align`. Opening it with the low-level tag writer instead does not compile outside
+//! of an `unsafe` block:
+//!
+//! ```compile_fail,E0133
+//! use nalgebra_latex::{
+//!     env::{AlignEnvironment, AlignedEnvironment, LatexEnvironment},
+//!     error::Error,
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::InnerParagraphMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! let w = Writer::<AmsLatex, NoFeatures, InnerParagraphMode, String>::default();
+//! let w = AlignEnvironment::with_env(w, |w| {
+//!     AlignedEnvironment::write_opening_tag(w).map_err(Error::from)
+//! })
+//! .unwrap();
+//! ```
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+
+use core::marker::PhantomData;
+
 use crate::{
-    latex_features::LatexFeatures, latex_flavors::LatexFlavor, latex_modes::LatexMode,
+    error::Error,
+    latex_features::{LatexFeatures, Package},
+    latex_flavors::{AmsLatex, LatexFlavor},
+    latex_modes::{DisplayMathMode, LatexMode, MathLatexMode, ParagraphLatexMode},
     latex_writer::LatexWriter,
+    text::RawLatex,
 };
 
+/// Implementors are environments that can be entered from the mode `M`.
+///
+/// # Safety
+///
+/// [`write_name`](LatexEnvironment::write_name) must write a valid name of an environment
+/// that can be entered from `M` and that makes [LaTeX] enter
+/// [`InnerMode`](LatexEnvironment::InnerMode).
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
 pub unsafe trait LatexEnvironment<Fl, Fe, M, W, InitW>
 where
     Fl: LatexFlavor,
@@ -11,23 +122,368 @@ where
     W: core::fmt::Write,
     InitW: LatexWriter<Flavor = Fl, Features = Fe, NestedWriter = W>,
 {
-    type InterWriter: LatexWriter<Flavor = Fl, Features = Fe, NestedWriter = W>; // Mode can change
+    /// The mode inside of the environment
+    type InnerMode: LatexMode;
 
-    type OWriter: LatexWriter<Flavor = Fl, Features = Fe, NestedWriter = W>; // Mode can change
+    /// The package that provides the environment, if any
+    const PACKAGE: Option<Package> = None;
 
     fn write_name(w: &mut W) -> Result<(), core::fmt::Error>;
-    fn write_opening_tag(w: InitW) -> Result<Self::InterWriter, core::fmt::Error> {
-        let (mut nested_writer, features) = w.into_raw_parts();
+    /// Writes the opening tag and enters [`InnerMode`](LatexEnvironment::InnerMode).
+    ///
+    /// # Safety
+    ///
+    /// The environment must be closed with [`write_closing_tag`](LatexEnvironment::write_closing_tag)
+    /// of the same environment after all environments nested in it are closed.
+    /// Use [`with_env`](LatexEnvironment::with_env) to have it checked at compile time.
+    unsafe fn write_opening_tag(
+        w: InitW,
+    ) -> Result<InitW::WithMode<Self::InnerMode>, Error> {
+        let (mut nested_writer, mut features) = w.into_raw_parts();
+        if let Some(package) = Self::PACKAGE {
+            features.require_package(package);
+        }
         nested_writer.write_str(r"\begin{")?;
         Self::write_name(&mut nested_writer)?;
         nested_writer.write_char('}')?;
-        Ok(unsafe { Self::InterWriter::from_raw_parts(nested_writer, features) })
+        Ok(unsafe { <InitW::WithMode<Self::InnerMode>>::from_raw_parts(nested_writer, features) })
     }
-    fn write_closing_tag(w: Self::InterWriter) -> Result<Self::OWriter, core::fmt::Error> {
+    /// Writes the closing tag and returns to the mode the environment was entered from.
+    ///
+    /// # Safety
+    ///
+    /// The innermost open environment must be this one.
+    unsafe fn write_closing_tag(
+        w: InitW::WithMode<Self::InnerMode>,
+    ) -> Result<InitW, Error> {
         let (mut nested_writer, features) = w.into_raw_parts();
         nested_writer.write_str(r"\end{")?;
         Self::write_name(&mut nested_writer)?;
         nested_writer.write_char('}')?;
-        Ok(unsafe { Self::OWriter::from_raw_parts(nested_writer, features) })
+        Ok(unsafe { InitW::from_raw_parts(nested_writer, features) })
+    }
+    /// Writes the opening tag, the body with `f` and the matching closing tag.
+    ///
+    /// Unlike the pair of [`write_opening_tag`](LatexEnvironment::write_opening_tag) and
+    /// [`write_closing_tag`](LatexEnvironment::write_closing_tag), the environment
+    /// can be neither left unclosed nor closed with another environment.
+    fn with_env<E, F>(w: InitW, f: F) -> Result<InitW, E>
+    where
+        E: From<Error>,
+        F: FnOnce(InitW::WithMode<Self::InnerMode>) -> Result<InitW::WithMode<Self::InnerMode>, E>,
+    {
+        let w = unsafe { Self::write_opening_tag(w) }?;
+        let w = f(w)?;
+        Ok(unsafe { Self::write_closing_tag(w) }?)
+    }
+}
+
+/// Implementors are environments whose opening tag is followed by arguments,
+/// e.g. `\begin{array}[t]{cc}`.
+///
+/// # Safety
+///
+/// The arguments written by [`write_args`](LatexEnvironmentWithArgs::write_args) must be valid
+/// [LaTeX] and the environment must make [LaTeX] enter [`InnerMode`](LatexEnvironmentWithArgs::InnerMode).
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+pub unsafe trait LatexEnvironmentWithArgs<Fl, Fe, M, W, InitW>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: LatexMode,
+    W: core::fmt::Write,
+    InitW: LatexWriter<Flavor = Fl, Features = Fe, NestedWriter = W>,
+{
+    /// The mode inside of the environment
+    type InnerMode: LatexMode;
+
+    /// The package that provides the environment, if any
+    const PACKAGE: Option<Package> = None;
+
+    fn write_name(&self, w: &mut W) -> Result<(), core::fmt::Error>;
+    /// Writes the optional `[...]` and mandatory `{...}` arguments of the environment
+    fn write_args(&self, w: &mut W) -> Result<(), core::fmt::Error>;
+    /// Writes the opening tag with the arguments and enters
+    /// [`InnerMode`](LatexEnvironmentWithArgs::InnerMode).
+    ///
+    /// # Safety
+    ///
+    /// See [`LatexEnvironment::write_opening_tag`].
+    unsafe fn write_opening_tag_with_args(
+        &self,
+        w: InitW,
+    ) -> Result<InitW::WithMode<Self::InnerMode>, Error> {
+        let (mut nested_writer, mut features) = w.into_raw_parts();
+        if let Some(package) = Self::PACKAGE {
+            features.require_package(package);
+        }
+        nested_writer.write_str(r"\begin{")?;
+        self.write_name(&mut nested_writer)?;
+        nested_writer.write_char('}')?;
+        self.write_args(&mut nested_writer)?;
+        Ok(unsafe { <InitW::WithMode<Self::InnerMode>>::from_raw_parts(nested_writer, features) })
+    }
+    /// Writes the closing tag and returns to the mode the environment was entered from.
+    ///
+    /// # Safety
+    ///
+    /// See [`LatexEnvironment::write_closing_tag`].
+    unsafe fn write_closing_tag_with_args(
+        &self,
+        w: InitW::WithMode<Self::InnerMode>,
+    ) -> Result<InitW, Error> {
+        let (mut nested_writer, features) = w.into_raw_parts();
+        nested_writer.write_str(r"\end{")?;
+        self.write_name(&mut nested_writer)?;
+        nested_writer.write_char('}')?;
+        Ok(unsafe { InitW::from_raw_parts(nested_writer, features) })
+    }
+    /// Writes the opening tag with the arguments, the body with `f` and the matching closing tag.
+    ///
+    /// See [`LatexEnvironment::with_env`].
+    fn with_env_with_args<E, F>(&self, w: InitW, f: F) -> Result<InitW, E>
+    where
+        E: From<Error>,
+        F: FnOnce(InitW::WithMode<Self::InnerMode>) -> Result<InitW::WithMode<Self::InnerMode>, E>,
+    {
+        let w = unsafe { self.write_opening_tag_with_args(w) }?;
+        let w = f(w)?;
+        Ok(unsafe { self.write_closing_tag_with_args(w) }?)
+    }
+}
+
+/// Implementors are environments with a starred variant that differs only in the
+/// numbering, e.g. `align*`.
+///
+/// # Safety
+///
+/// The starred variant must make [LaTeX] enter the same mode as the environment.
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+pub unsafe trait StarrableEnvironment {
+    /// The package that provides the starred variant, if any
+    const STARRED_PACKAGE: Option<Package>;
+}
+
+/// The starred variant of the environment `E`, e.g. `align*` for [`AlignEnvironment`]
+pub struct Starred<E>(PhantomData<E>);
+
+unsafe impl<Fl, Fe, M, W, InitW, E> LatexEnvironment<Fl, Fe, M, W, InitW> for Starred<E>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: LatexMode,
+    W: core::fmt::Write,
+    InitW: LatexWriter<Flavor = Fl, Features = Fe, NestedWriter = W>,
+    E: LatexEnvironment<Fl, Fe, M, W, InitW> + StarrableEnvironment,
+{
+    type InnerMode = E::InnerMode;
+    const PACKAGE: Option<Package> = E::STARRED_PACKAGE;
+
+    fn write_name(w: &mut W) -> Result<(), core::fmt::Error> {
+        E::write_name(w)?;
+        w.write_char('*')
+    }
+}
+
+macro_rules! decl_display_math_environments {
+    ($($(#[$attr:meta])* $env:ident => $name:literal, $package:expr, $starred_package:expr;)+) => {
+        $(
+            $(#[$attr])*
+            pub struct $env;
+
+            unsafe impl<Fl, Fe, M, W, InitW> LatexEnvironment<Fl, Fe, M, W, InitW> for $env
+            where
+                Fl: LatexFlavor,
+                Fe: LatexFeatures,
+                M: ParagraphLatexMode,
+                W: core::fmt::Write,
+                InitW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = W>,
+            {
+                type InnerMode = DisplayMathMode;
+                const PACKAGE: Option<Package> = $package;
+                fn write_name(w: &mut W) -> Result<(), core::fmt::Error> {
+                    w.write_str($name)
+                }
+            }
+
+            unsafe impl StarrableEnvironment for $env {
+                const STARRED_PACKAGE: Option<Package> = $starred_package;
+            }
+        )+
+    };
+}
+
+decl_display_math_environments! {
+    /// `equation` environment, i.e. a numbered equation
+    EquationEnvironment => "equation", None, Some(Package::Amsmath);
+    /// `align` environment of [`amsmath`](https://ctan.org/pkg/amsmath), i.e. numbered
+    /// equations aligned at `&`
+    AlignEnvironment => "align", Some(Package::Amsmath), Some(Package::Amsmath);
+    /// `gather` environment of [`amsmath`](https://ctan.org/pkg/amsmath), i.e. numbered
+    /// centered equations
+    GatherEnvironment => "gather", Some(Package::Amsmath), Some(Package::Amsmath);
+    /// `multline` environment of [`amsmath`](https://ctan.org/pkg/amsmath), i.e. a numbered
+    /// equation broken into lines
+    MultlineEnvironment => "multline", Some(Package::Amsmath), Some(Package::Amsmath);
+}
+
+/// `split` environment of [`amsmath`](https://ctan.org/pkg/amsmath), i.e. a single equation
+/// broken into lines aligned at `&`, which can be used only inside of display math
+pub struct SplitEnvironment;
+
+unsafe impl<Fl, Fe, W, InitW> LatexEnvironment<Fl, Fe, DisplayMathMode, W, InitW>
+    for SplitEnvironment
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    W: core::fmt::Write,
+    InitW: LatexWriter<Flavor = Fl, Features = Fe, Mode = DisplayMathMode, NestedWriter = W>,
+{
+    type InnerMode = DisplayMathMode;
+    const PACKAGE: Option<Package> = Some(Package::Amsmath);
+    fn write_name(w: &mut W) -> Result<(), core::fmt::Error> {
+        w.write_str("split")
+    }
+}
+
+/// `aligned` environment of [`amsmath`](https://ctan.org/pkg/amsmath), i.e. equations
+/// aligned at `&` that can be used only inside of math
+pub struct AlignedEnvironment;
+
+unsafe impl<Fl, Fe, M, W, InitW> LatexEnvironment<Fl, Fe, M, W, InitW> for AlignedEnvironment
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    W: core::fmt::Write,
+    InitW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = W>,
+{
+    type InnerMode = M;
+    const PACKAGE: Option<Package> = Some(Package::Amsmath);
+    fn write_name(w: &mut W) -> Result<(), core::fmt::Error> {
+        w.write_str("aligned")
+    }
+}
+
+/// `subequations` environment of [`amsmath`](https://ctan.org/pkg/amsmath), i.e. a group of
+/// equations numbered as subequations, e.g. `2a`, `2b`, which is supported only by [`AmsLatex`].
+///
+/// The labels of the group are written with
+/// [`Subequations`](crate::fmt::labels::Subequations).
+///
+/// # Example
+///
+/// ```
+/// use nalgebra_latex::{
+///     env::{AlignEnvironment, LatexEnvironment, SubequationsEnvironment},
+///     error::Error,
+///     fmt::labels::{Counters, Subequations},
+///     latex_features::{NoFeatures, Package},
+///     latex_flavors::AmsLatex,
+///     latex_modes::InnerParagraphMode,
+///     latex_writer::{LatexWriter, Writer},
+///     text::{RawLatex, WriteRawLatex},
+/// };
+///
+/// type W = Writer<AmsLatex, NoFeatures, InnerParagraphMode, String>;
+///
+/// let mut counters = Counters::new();
+/// let w = SubequationsEnvironment::with_env(W::default().track_packages(), |w| {
+///     AlignEnvironment::with_env(w, |mut w| {
+///         let mut subequations = Subequations::new(&mut counters);
+///         w.write_raw(RawLatex::new(r"x&=1").unwrap())?;
+///         unsafe { subequations.write_next_label(&mut w) }?;
+///         w.write_raw(RawLatex::new(r"\\y&=2").unwrap())?;
+///         unsafe { subequations.write_next_label(&mut w) }?;
+///         Ok::<_, Error>(w)
+///     })
+/// })
+/// .unwrap();
+/// assert!(w.required_packages().contains(Package::Amsmath));
+/// let (s, _required_packages) = w.into_raw_parts();
+/// assert_eq!(
+///     s,
+///     concat!(
+///         r"\begin{subequations}\begin{align}",
+///         r"x&=1\tag{1a}\label{1a}\\y&=2\tag{1b}\label{1b}",
+///         r"\end{align}\end{subequations}",
+///     )
+/// );
+/// ```
+pub struct SubequationsEnvironment;
+
+unsafe impl<Fe, M, W, InitW> LatexEnvironment<AmsLatex, Fe, M, W, InitW> for SubequationsEnvironment
+where
+    Fe: LatexFeatures,
+    M: ParagraphLatexMode,
+    W: core::fmt::Write,
+    InitW: LatexWriter<Flavor = AmsLatex, Features = Fe, Mode = M, NestedWriter = W>,
+{
+    type InnerMode = M;
+    const PACKAGE: Option<Package> = Some(Package::Amsmath);
+    fn write_name(w: &mut W) -> Result<(), core::fmt::Error> {
+        w.write_str("subequations")
+    }
+}
+
+/// The vertical position of [`ArrayEnvironment`] relative to the baseline
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArrayPosition {
+    /// `[t]`, i.e. the top row is on the baseline
+    Top,
+    /// `[b]`, i.e. the bottom row is on the baseline
+    Bottom,
+}
+
+/// `array` environment, i.e. a table in math mode with the column specification,
+/// e.g. `cc|c`
+#[derive(Clone, Copy)]
+pub struct ArrayEnvironment<'a> {
+    columns: RawLatex<'a>,
+    position: Option<ArrayPosition>,
+}
+
+impl<'a> ArrayEnvironment<'a> {
+    pub fn new(columns: RawLatex<'a>) -> Self {
+        Self {
+            columns,
+            position: None,
+        }
+    }
+
+    pub fn with_position(self, position: ArrayPosition) -> Self {
+        Self {
+            position: Some(position),
+            ..self
+        }
+    }
+}
+
+unsafe impl<'a, Fl, Fe, M, W, InitW> LatexEnvironmentWithArgs<Fl, Fe, M, W, InitW>
+    for ArrayEnvironment<'a>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    W: core::fmt::Write,
+    InitW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = W>,
+{
+    type InnerMode = M;
+
+    fn write_name(&self, w: &mut W) -> Result<(), core::fmt::Error> {
+        w.write_str("array")
+    }
+
+    fn write_args(&self, w: &mut W) -> Result<(), core::fmt::Error> {
+        match self.position {
+            Some(ArrayPosition::Top) => w.write_str("[t]")?,
+            Some(ArrayPosition::Bottom) => w.write_str("[b]")?,
+            None => {}
+        }
+        w.write_char('{')?;
+        w.write_str(self.columns.as_str())?;
+        w.write_char('}')
     }
 }
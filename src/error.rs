@@ -0,0 +1,105 @@
+//! Module with the error type of the crate.
+//!
+//! Writing to the nested writer can fail only with the opaque [`core::fmt::Error`], which is
+//! converted into [`Error::Fmt`]. The other variants describe why the crate itself refused
+//! to write [LaTeX] so that the caller can tell a broken output stream from a bad input.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra_latex::error::Error;
+//!
+//! assert_eq!(
+//!     Error::DimensionMismatch { expected: 3, found: 2 }.to_string(),
+//!     "dimension mismatch: expected 3, found 2"
+//! );
+//!
+//! # #[cfg(feature = "lin_sys")]
+//! # {
+//! use nalgebra_latex::{
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::InlineMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//!     lin_sys::unknowns::{SingleLetterBoldfaceVecOfDistinctSingleLetterUnknowns, Unknowns},
+//! };
+//!
+//! let unknowns = SingleLetterBoldfaceVecOfDistinctSingleLetterUnknowns::new('x', ['a', 'b']);
+//! let mut w = Writer::<AmsLatex, NoFeatures, InlineMathMode, String>::default();
+//! assert_eq!(
+//!     unknowns.write_ith(&mut w, 2),
+//!     Err(Error::IndexOutOfBounds { index: 2, len: 2 })
+//! );
+//! # }
+//! ```
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+
+use core::fmt::{Display, Formatter};
+
+use crate::text::InvalidRawLatexError;
+
+/// The error type of the formatters and writers of the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Writing to the nested writer failed, e.g. because of an I/O error
+    Fmt(core::fmt::Error),
+    /// The dimensions of the input are inconsistent, e.g. the linear system has more
+    /// columns than there are unknowns. `expected` is the least acceptable dimension.
+    DimensionMismatch { expected: usize, found: usize },
+    /// The zero-based index is out of bounds, e.g. there is no unknown with the index
+    IndexOutOfBounds { index: usize, len: usize },
+    /// The label generator has run out of labels
+    LabelExhaustion,
+    /// The flavor does not support the construct
+    UnsupportedByFlavor { construct: &'static str },
+    /// The input cannot be written as valid [LaTeX]
+    ///
+    /// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+    InvalidInput { reason: &'static str },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Fmt(_) => f.write_str("failed to write to the nested writer"),
+            Error::DimensionMismatch { expected, found } => {
+                write!(f, "dimension mismatch: expected {}, found {}", expected, found)
+            }
+            Error::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for length {}", index, len)
+            }
+            Error::LabelExhaustion => f.write_str("the label generator has run out of labels"),
+            Error::UnsupportedByFlavor { construct } => {
+                write!(f, "{} is not supported by the LaTeX flavor", construct)
+            }
+            Error::InvalidInput { reason } => write!(f, "invalid input: {}", reason),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Fmt(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<core::fmt::Error> for Error {
+    fn from(e: core::fmt::Error) -> Self {
+        Error::Fmt(e)
+    }
+}
+
+impl From<InvalidRawLatexError> for Error {
+    fn from(_e: InvalidRawLatexError) -> Self {
+        Error::InvalidInput {
+            reason: "unbalanced braces, an unescaped % or $, or a dangling backslash",
+        }
+    }
+}
+
@@ -0,0 +1,188 @@
+//! Module with rich display of formatted values in [`evcxr`]-based [Jupyter] notebooks.
+//!
+//! [`evcxr`] renders the last expression of a cell by calling its inherent `evcxr_display`
+//! method, if any. [`EvcxrLatex`] pairs a value with a [`LatexFormatter`] and offers such a
+//! method, which emits the formatted value as a display math block twice: once as
+//! `text/latex` and once as `text/html` ready to be typeset by [MathJax] for the frontends
+//! that do not render `text/latex` themselves.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra::matrix;
+//! use nalgebra_latex::{evcxr::EvcxrLatex, matrix::fmt::BracketedMatrixFormatter};
+//!
+//! let m = EvcxrLatex::<_, BracketedMatrixFormatter>::new(matrix![1, 2; 3, 4]);
+//! assert_eq!(
+//!     m.to_latex().unwrap(),
+//!     r"$$\begin{bmatrix}1&2\\3&4\end{bmatrix}$$"
+//! );
+//!
+//! let mut s = String::new();
+//! m.write_evcxr_content(&mut s).unwrap();
+//! assert_eq!(
+//!     s,
+//!     concat!(
+//!         "EVCXR_BEGIN_CONTENT text/latex\n",
+//!         "$$\\begin{bmatrix}1&2\\\\3&4\\end{bmatrix}$$\n",
+//!         "EVCXR_END_CONTENT\n",
+//!         "EVCXR_BEGIN_CONTENT text/html\n",
+//!         "<div class=\"nalgebra-latex\">$$\\begin{bmatrix}1&amp;2\\\\3&amp;4\\end{bmatrix}$$</div>\n",
+//!         "EVCXR_END_CONTENT\n",
+//!     )
+//! );
+//!
+//! # #[cfg(feature = "lin_sys")]
+//! # {
+//! use nalgebra::Const;
+//! use nalgebra_latex::lin_sys::{
+//!     fmt::CasesLinSysFormatter,
+//!     numbering::Numbering,
+//!     unknowns::SingleLetterBoldfaceVecOfUnknowns,
+//!     LinSys,
+//! };
+//!
+//! let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<2>);
+//! let lin_sys = LinSys::new(matrix![1, 2, 3; 4, 5, 6], unknowns).unwrap();
+//! let lin_sys = EvcxrLatex::<_, CasesLinSysFormatter>::new(lin_sys);
+//! assert_eq!(
+//!     lin_sys.to_latex().unwrap(),
+//!     r"$$\begin{cases}1x_{1}+2x_{2}=3\\4x_{1}+5x_{2}=6\end{cases}$$"
+//! );
+//! # }
+//! ```
+//!
+//! [`evcxr`]: https://github.com/google/evcxr
+//! [Jupyter]: https://jupyter.org/
+//! [MathJax]: https://www.mathjax.org/
+
+use core::{fmt::Write, marker::PhantomData};
+
+use mime_typed::{evcxr_support::TextHtml, Mime, MimeStrExt};
+
+use crate::{
+    error::Error,
+    fmt::LatexFormatter,
+    latex_features::NoFeatures,
+    latex_flavors::MathJax,
+    latex_modes::DisplayMathMode,
+    latex_writer::{LatexWriter, Writer},
+};
+
+/// Type for `text/latex` MIME type, which is not offered by [`mime_typed`].
+pub struct TextLatex;
+
+impl MimeStrExt for TextLatex {
+    const MIME_STR: &'static str = "text/latex";
+}
+
+impl core::fmt::Display for TextLatex {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(Self::MIME_STR)
+    }
+}
+
+impl From<TextLatex> for String {
+    fn from(_mime: TextLatex) -> Self {
+        TextLatex::MIME_STR.to_string()
+    }
+}
+
+impl Mime for TextLatex {}
+
+/// Wrapper of a value that is displayed in [`evcxr`] as typeset math formatted with `F`.
+///
+/// The value is formatted for [`MathJax`] flavor because it is [MathJax] that typesets
+/// math in [Jupyter] frontends.
+///
+/// [`evcxr`]: https://github.com/google/evcxr
+/// [Jupyter]: https://jupyter.org/
+/// [MathJax]: https://www.mathjax.org/
+pub struct EvcxrLatex<T, F> {
+    value: T,
+    formatter: PhantomData<F>,
+}
+
+impl<T, F> EvcxrLatex<T, F> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            formatter: PhantomData,
+        }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, F> EvcxrLatex<T, F>
+where
+    F: LatexFormatter<MathJax, NoFeatures, NoFeatures, DisplayMathMode, DisplayMathMode, T>,
+{
+    /// Returns the value formatted as a display math block, i.e. delimited with `$$`.
+    pub fn to_latex(&self) -> Result<String, Error> {
+        let w = unsafe {
+            Writer::<MathJax, NoFeatures, DisplayMathMode, String>::new(
+                "$$".to_string(),
+                NoFeatures,
+            )
+        };
+        let w: Writer<MathJax, NoFeatures, DisplayMathMode, String> = F::fmt(w, &self.value)?;
+        let (mut s, _no_features) = w.into_raw_parts();
+        s.push_str("$$");
+        Ok(s)
+    }
+
+    /// Writes the `text/latex` and `text/html` contents in the format expected by [`evcxr`].
+    ///
+    /// [`evcxr`]: https://github.com/google/evcxr
+    pub fn write_evcxr_content<W: Write>(&self, dest: &mut W) -> Result<(), Error> {
+        let latex = self.to_latex()?;
+        write_content(dest, TextLatex, |dest| dest.write_str(&latex))?;
+        write_content(dest, TextHtml, |dest| {
+            dest.write_str("<div class=\"nalgebra-latex\">")?;
+            write_html_escaped(dest, &latex)?;
+            dest.write_str("</div>")
+        })?;
+        Ok(())
+    }
+
+    /// Prints the contents for [`evcxr`] to display the value as typeset math.
+    ///
+    /// [`evcxr`]: https://github.com/google/evcxr
+    pub fn evcxr_display(&self) {
+        let mut s = String::new();
+        match self.write_evcxr_content(&mut s) {
+            Ok(()) => print!("{}", s),
+            Err(e) => eprintln!("nalgebra_latex: failed to format the value as LaTeX: {}", e),
+        }
+    }
+}
+
+fn write_content<W, M, C>(dest: &mut W, mime: M, content: C) -> core::fmt::Result
+where
+    W: Write,
+    M: Mime,
+    C: FnOnce(&mut W) -> core::fmt::Result,
+{
+    writeln!(dest, "EVCXR_BEGIN_CONTENT {}", mime)?;
+    content(dest)?;
+    dest.write_str("\nEVCXR_END_CONTENT\n")
+}
+
+fn write_html_escaped<W: Write>(dest: &mut W, s: &str) -> core::fmt::Result {
+    for c in s.chars() {
+        match c {
+            '&' => dest.write_str("&amp;")?,
+            '<' => dest.write_str("&lt;")?,
+            '>' => dest.write_str("&gt;")?,
+            c => dest.write_char(c)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! Module with a trait for querying coefficients of terms, e.g. of linear equations,
+//! so that the terms can be written the way they are written by hand.
+//!
+//! For example, `1x_1+-3x_2+0x_3` is conventionally written as `x_1-3x_2`.
+
+/// Queries about the value of a coefficient of a term.
+pub trait Coefficient {
+    /// The type of the absolute value of the coefficient, e.g. [`u8`] for [`i8`]
+    type Abs: Coefficient;
+
+    fn is_zero(&self) -> bool;
+
+    fn is_one(&self) -> bool;
+
+    fn is_negative(&self) -> bool;
+
+    fn abs(&self) -> Self::Abs;
+}
+
+macro_rules! impl_for_unsigned {
+    ($t:ident) => {
+        impl Coefficient for $t {
+            type Abs = $t;
+
+            #[inline(always)]
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+
+            #[inline(always)]
+            fn is_one(&self) -> bool {
+                *self == 1
+            }
+
+            #[inline(always)]
+            fn is_negative(&self) -> bool {
+                false
+            }
+
+            #[inline(always)]
+            fn abs(&self) -> Self::Abs {
+                *self
+            }
+        }
+    };
+}
+
+macro_rules! impl_for_signed {
+    ($t:ident, $abs:ident) => {
+        impl Coefficient for $t {
+            type Abs = $abs;
+
+            #[inline(always)]
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+
+            #[inline(always)]
+            fn is_one(&self) -> bool {
+                *self == 1
+            }
+
+            #[inline(always)]
+            fn is_negative(&self) -> bool {
+                *self < 0
+            }
+
+            #[inline(always)]
+            fn abs(&self) -> Self::Abs {
+                self.unsigned_abs()
+            }
+        }
+    };
+}
+
+macro_rules! impl_for_float {
+    ($t:ident) => {
+        impl Coefficient for $t {
+            type Abs = $t;
+
+            #[inline(always)]
+            fn is_zero(&self) -> bool {
+                *self == 0.0
+            }
+
+            #[inline(always)]
+            fn is_one(&self) -> bool {
+                *self == 1.0
+            }
+
+            #[inline(always)]
+            fn is_negative(&self) -> bool {
+                self.is_sign_negative() && !self.is_zero() && !self.is_nan()
+            }
+
+            #[inline(always)]
+            fn abs(&self) -> Self::Abs {
+                if self.is_negative() {
+                    -*self
+                } else {
+                    *self
+                }
+            }
+        }
+    };
+}
+
+impl_for_unsigned!(u8);
+impl_for_unsigned!(u16);
+impl_for_unsigned!(u32);
+impl_for_unsigned!(u64);
+impl_for_unsigned!(u128);
+impl_for_unsigned!(usize);
+impl_for_signed!(i8, u8);
+impl_for_signed!(i16, u16);
+impl_for_signed!(i32, u32);
+impl_for_signed!(i64, u64);
+impl_for_signed!(i128, u128);
+impl_for_signed!(isize, usize);
+impl_for_float!(f32);
+impl_for_float!(f64);
+
+#[cfg(feature = "rational")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "rational")))]
+impl<T> Coefficient for num_rational::Ratio<T>
+where
+    T: Clone + num_integer::Integer,
+{
+    type Abs = num_rational::Ratio<T>;
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.numer().is_zero()
+    }
+
+    #[inline(always)]
+    fn is_one(&self) -> bool {
+        *self.numer() == *self.denom()
+    }
+
+    #[inline(always)]
+    fn is_negative(&self) -> bool {
+        super::impl_num_rational::split_sign(self).0
+    }
+
+    #[inline(always)]
+    fn abs(&self) -> Self::Abs {
+        let (_is_negative, numer, denom) = super::impl_num_rational::split_sign(self);
+        num_rational::Ratio::new_raw(numer, denom)
+    }
+}
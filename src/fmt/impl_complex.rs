@@ -0,0 +1,150 @@
+use core::fmt::Write;
+
+use nalgebra::Complex;
+
+use super::{
+    coefficient::Coefficient,
+    number::{ComplexForm, ComplexPart, ImaginaryUnit},
+    PartialEndofunctionalWriteAsLatex, WriteAsLatex,
+};
+use crate::{
+    error::Error,
+    latex_features::LatexFeatures,
+    latex_flavors::{LatexFlavor, LatexFlavorKindExt},
+    latex_modes::LatexModeKindExt,
+    latex_writer::LatexWriter,
+};
+#[cfg(feature = "std")]
+use crate::latex_modes::{LatexMode, LatexModeCategory};
+
+// Writes the complex number in the form of the number format, writing the parts with the closures.
+fn write_complex<T, W, F, G>(
+    z: &Complex<T>,
+    dest: W,
+    write_part: F,
+    write_abs: G,
+) -> Result<W, Error>
+where
+    T: ComplexPart,
+    W: LatexWriter,
+    W::Mode: LatexModeKindExt,
+    F: Fn(&T, W) -> Result<W, Error>,
+    G: Fn(&T::Abs, W) -> Result<W, Error>,
+{
+    let (nested_writer, features) = dest.into_raw_parts();
+    let number_format = features.number_format();
+    let mut dest = unsafe { W::from_raw_parts(nested_writer, features) };
+    let unit = match number_format.imaginary_unit() {
+        ImaginaryUnit::I => 'i',
+        ImaginaryUnit::J => 'j',
+    };
+    match number_format.complex_form() {
+        ComplexForm::Cartesian => {
+            if z.im.is_zero() {
+                return write_part(&z.re, dest);
+            }
+            if !z.re.is_zero() {
+                dest = write_part(&z.re, dest)?;
+                if !z.im.is_negative() {
+                    unsafe { dest.write_char('+') }?;
+                }
+            }
+            if z.im.is_negative() {
+                unsafe { dest.write_char('-') }?;
+            }
+            let abs = z.im.abs();
+            if !abs.is_one() {
+                dest = write_abs(&abs, dest)?;
+            }
+            unsafe { dest.write_char(unit) }?;
+            Ok(dest)
+        }
+        #[cfg(not(feature = "std"))]
+        ComplexForm::Polar => Err(Error::InvalidInput {
+            reason: "the polar form of complex numbers requires the std feature",
+        }),
+        #[cfg(feature = "std")]
+        ComplexForm::Polar => {
+            let (re, im) = (z.re.to_f64(), z.im.to_f64());
+            let (r, theta) = (re.hypot(im), im.atan2(re));
+            // The exponent needs math mode
+            let needs_math = W::Mode::KIND.category() != LatexModeCategory::Math;
+            let (mut nested_writer, mut features) = dest.into_raw_parts();
+            if needs_math {
+                nested_writer.write_char('$')?;
+            }
+            if r == 0.0 {
+                nested_writer.write_char('0')?;
+            } else {
+                if r != 1.0 || theta == 0.0 {
+                    number_format.write_float::<W::Flavor, _, _, _>(
+                        &mut nested_writer,
+                        &mut features,
+                        r,
+                    )?;
+                }
+                if theta != 0.0 {
+                    nested_writer.write_str("e^{")?;
+                    if theta < 0.0 {
+                        nested_writer.write_char('-')?;
+                    }
+                    nested_writer.write_char(unit)?;
+                    number_format.write_float::<W::Flavor, _, _, _>(
+                        &mut nested_writer,
+                        &mut features,
+                        theta.abs(),
+                    )?;
+                    nested_writer.write_char('}')?;
+                }
+            }
+            if needs_math {
+                nested_writer.write_char('$')?;
+            }
+            Ok(unsafe { W::from_raw_parts(nested_writer, features) })
+        }
+    }
+}
+
+impl<Fl, Fe, M, NestedWriter, W, T> WriteAsLatex<Fl, Fe, Fe, M, M, NestedWriter, W, W>
+    for Complex<T>
+where
+    Fl: LatexFlavor + LatexFlavorKindExt,
+    Fe: LatexFeatures,
+    M: LatexModeKindExt,
+    NestedWriter: Write,
+    W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NestedWriter>,
+    T: ComplexPart + WriteAsLatex<Fl, Fe, Fe, M, M, NestedWriter, W, W>,
+    T::Abs: WriteAsLatex<Fl, Fe, Fe, M, M, NestedWriter, W, W>,
+{
+    fn write_as_latex(&self, dest: W) -> Result<W, Error> {
+        write_complex(
+            self,
+            dest,
+            |x: &T, w| x.write_as_latex(w),
+            |x: &T::Abs, w| x.write_as_latex(w),
+        )
+    }
+}
+
+impl<Fl, Fe, M, T> PartialEndofunctionalWriteAsLatex<Fl, Fe, M> for Complex<T>
+where
+    Fl: LatexFlavor + LatexFlavorKindExt,
+    Fe: LatexFeatures,
+    M: LatexModeKindExt,
+    T: ComplexPart + PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+{
+    #[inline(always)]
+    fn partial_endofunctional_write_as_latex<W, NW>(&self, dest: W) -> Result<W, Error>
+    where
+        NW: Write,
+        W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NW>,
+    {
+        write_complex(
+            self,
+            dest,
+            |x: &T, w| x.partial_endofunctional_write_as_latex::<W, NW>(w),
+            |x: &T::Abs, w| x.partial_endofunctional_write_as_latex::<W, NW>(w),
+        )
+    }
+}
@@ -3,7 +3,9 @@ use crate::{
     latex_features::LatexFeatures, latex_flavors::LatexFlavor, latex_modes::LatexMode,
     latex_writer::LatexWriter,
 };
-use core::fmt::{Error, Write};
+use core::fmt::Write;
+
+use crate::error::Error;
 
 macro_rules! impl_for_prim_numeric {
     ($t:ident) => {
@@ -49,6 +51,7 @@ impl<
         NestedWriter,
         InitalWriter,
         OutputWriter,
+        F,
     >
     ConsumingWriteAsLatex<
         Flavor,
@@ -59,7 +62,7 @@ impl<
         NestedWriter,
         InitalWriter,
         OutputWriter,
-    > for fn(InitalWriter) -> Result<OutputWriter, Error>
+    > for F
 where
     Flavor: LatexFlavor,
     InitialFeatures: LatexFeatures,
@@ -79,6 +82,9 @@ where
         Mode = ConsequentLatexMode,
         NestedWriter = NestedWriter,
     >,
+    // Closures cannot be coerced to function pointers during method resolution,
+    // so the implementation covers every FnOnce, function pointers included.
+    F: FnOnce(InitalWriter) -> Result<OutputWriter, Error>,
 {
     fn consuming_write_as_latex(self, dest: InitalWriter) -> Result<OutputWriter, Error> {
         (self)(dest)
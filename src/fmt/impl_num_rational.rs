@@ -0,0 +1,108 @@
+use core::fmt::{Display, Write};
+
+use num_integer::Integer;
+use num_rational::Ratio;
+
+use super::{number::FractionStyle, PartialEndofunctionalWriteAsLatex, WriteAsLatex};
+use crate::{
+    error::Error,
+    latex_features::LatexFeatures,
+    latex_flavors::{LatexFlavor, LatexFlavorKindExt},
+    latex_modes::{LatexMode, LatexModeCategory, LatexModeKindExt},
+    latex_writer::LatexWriter,
+};
+
+// Returns whether the ratio is negative and the absolute values of its numerator and denominator
+pub(crate) fn split_sign<T>(r: &Ratio<T>) -> (bool, T, T)
+where
+    T: Clone + Integer,
+{
+    let abs = |n: &T| {
+        if *n < T::zero() {
+            T::zero() - n.clone()
+        } else {
+            n.clone()
+        }
+    };
+    let is_negative = (*r.numer() < T::zero()) != (*r.denom() < T::zero());
+    (
+        is_negative && !r.numer().is_zero(),
+        abs(r.numer()),
+        abs(r.denom()),
+    )
+}
+
+impl<Fl, Fe, M, NestedWriter, W, T> WriteAsLatex<Fl, Fe, Fe, M, M, NestedWriter, W, W> for Ratio<T>
+where
+    Fl: LatexFlavor + LatexFlavorKindExt,
+    Fe: LatexFeatures,
+    M: LatexModeKindExt,
+    NestedWriter: Write,
+    W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NestedWriter>,
+    T: Clone + Integer + Display,
+{
+    fn write_as_latex(&self, dest: W) -> Result<W, Error> {
+        let (mut nested_writer, mut features) = dest.into_raw_parts();
+        let number_format = features.number_format();
+        let (is_negative, numer, denom) = split_sign(self);
+        if denom.is_one() || numer.is_zero() {
+            if is_negative {
+                nested_writer.write_char('-')?;
+            }
+            write!(nested_writer, "{}", numer)?;
+        } else {
+            let (whole, numer) = match number_format.fraction_style() {
+                FractionStyle::Mixed if numer >= denom => {
+                    let (whole, numer) = numer.div_rem(&denom);
+                    (Some(whole), numer)
+                }
+                _ => (None, numer),
+            };
+            match whole {
+                // E.g. `4/2` constructed with `Ratio::new_raw`
+                Some(whole) if numer.is_zero() => {
+                    if is_negative {
+                        nested_writer.write_char('-')?;
+                    }
+                    write!(nested_writer, "{}", whole)?;
+                }
+                whole => {
+                    // Only the slash is not a math mode command
+                    let needs_math = M::KIND.category() != LatexModeCategory::Math
+                        && number_format.fraction_style() != FractionStyle::Slash;
+                    if needs_math {
+                        nested_writer.write_char('$')?;
+                    }
+                    number_format.write_fraction::<Fl, _, _, _>(
+                        &mut nested_writer,
+                        &mut features,
+                        is_negative,
+                        whole,
+                        numer,
+                        denom,
+                    )?;
+                    if needs_math {
+                        nested_writer.write_char('$')?;
+                    }
+                }
+            }
+        }
+        Ok(unsafe { W::from_raw_parts(nested_writer, features) })
+    }
+}
+
+impl<Fl, Fe, M, T> PartialEndofunctionalWriteAsLatex<Fl, Fe, M> for Ratio<T>
+where
+    Fl: LatexFlavor + LatexFlavorKindExt,
+    Fe: LatexFeatures,
+    M: LatexModeKindExt,
+    T: Clone + Integer + Display,
+{
+    #[inline(always)]
+    fn partial_endofunctional_write_as_latex<W, NW>(&self, dest: W) -> Result<W, Error>
+    where
+        W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+    {
+        self.write_as_latex(dest)
+    }
+}
@@ -1,9 +1,9 @@
 use super::{PartialEndofunctionalWriteAsLatex, WriteAsLatex};
 use crate::{
-    latex_features::LatexFeatures, latex_flavors::LatexFlavor, latex_modes::LatexMode,
+    latex_features::LatexFeatures, latex_flavors::{LatexFlavor, LatexFlavorKindExt}, latex_modes::{LatexMode, LatexModeKindExt},
     latex_writer::LatexWriter,
 };
-use core::fmt::Error;
+use crate::error::Error;
 
 macro_rules! impl_for_prim_numeric {
     ($t:ident) => {
@@ -36,5 +36,25 @@ impl_for_prim_numeric!(i32);
 impl_for_prim_numeric!(i64);
 impl_for_prim_numeric!(i128);
 impl_for_prim_numeric!(isize);
-impl_for_prim_numeric!(f32);
-impl_for_prim_numeric!(f64);
+
+macro_rules! impl_for_float {
+    ($t:ident) => {
+        impl<Fl, Fe, M> PartialEndofunctionalWriteAsLatex<Fl, Fe, M> for $t
+        where
+            Fl: LatexFlavor + LatexFlavorKindExt,
+            Fe: LatexFeatures,
+            M: LatexModeKindExt,
+        {
+            #[inline(always)]
+            fn partial_endofunctional_write_as_latex<W, NW>(&self, dest: W) -> Result<W, Error>
+            where
+                W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+            {
+                self.write_as_latex(dest)
+            }
+        }
+    };
+}
+
+impl_for_float!(f32);
+impl_for_float!(f64);
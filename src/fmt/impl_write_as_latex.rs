@@ -1,9 +1,12 @@
-use super::{WriteAsLatex, labels::{CountersLabel, Label, SupportedFlavor}};
+use super::{WriteAsLatex, labels::{CountersLabel, CustomLabel, Label, SupportedFlavor}};
 use crate::{
-    latex_features::LatexFeatures, latex_flavors::LatexFlavor, latex_modes::LatexMode,
+    latex_features::{LatexFeatures, Package}, latex_flavors::{LatexFlavor, LatexFlavorKindExt},
+    latex_modes::{LatexMode, LatexModeCategory, LatexModeKindExt},
     latex_writer::LatexWriter,
 };
-use core::fmt::{Error, Write};
+use core::fmt::Write;
+
+use crate::error::Error;
 
 macro_rules! impl_for_prim_numeric {
     ($t:ident) => {
@@ -36,8 +39,37 @@ impl_for_prim_numeric!(i32);
 impl_for_prim_numeric!(i64);
 impl_for_prim_numeric!(i128);
 impl_for_prim_numeric!(isize);
-impl_for_prim_numeric!(f32);
-impl_for_prim_numeric!(f64);
+
+macro_rules! impl_for_float {
+    ($t:ident) => {
+        impl<Fl, Fe, M, NestedWriter, W> WriteAsLatex<Fl, Fe, Fe, M, M, NestedWriter, W, W> for $t
+        where
+            Fl: LatexFlavor + LatexFlavorKindExt,
+            Fe: LatexFeatures,
+            M: LatexModeKindExt,
+            NestedWriter: Write,
+            W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NestedWriter>,
+        {
+            fn write_as_latex(&self, dest: W) -> Result<W, Error> {
+                let (mut nested_writer, mut features) = dest.into_raw_parts();
+                let number_format = features.number_format();
+                let needs_math = M::KIND.category() != LatexModeCategory::Math
+                    && !number_format.writes_text(*self);
+                if needs_math {
+                    nested_writer.write_char('$')?;
+                }
+                number_format.write_float::<Fl, _, _, _>(&mut nested_writer, &mut features, *self)?;
+                if needs_math {
+                    nested_writer.write_char('$')?;
+                }
+                Ok(unsafe { W::from_raw_parts(nested_writer, features) })
+            }
+        }
+    };
+}
+
+impl_for_float!(f32);
+impl_for_float!(f64);
 
 impl<
         Flavor,
@@ -84,32 +116,35 @@ where
     }
 }
 
-impl<Fl,Fe,M,W,IW,OW> WriteAsLatex<Fl, Fe, Fe, M, M, W, IW, OW>
-    for CountersLabel
-where
-    Fl: SupportedFlavor,
-    Fe: LatexFeatures,
-    M: LatexMode,
-    W: Write,
-    IW: LatexWriter<
-        Flavor = Fl,
-        Features = Fe,
-        Mode = M,
-        NestedWriter = W,
-    >,
-    OW: LatexWriter<
-        Flavor = Fl,
-        Features = Fe,
-        Mode = M,
-        NestedWriter = W,
-    >,
-{
-    fn write_as_latex(&self, dest: IW) -> Result<OW, Error> {
-        let (mut nested_writer, features) = dest.into_raw_parts();
-        let is_referencable =  Fl::is_referencable(self);
-        nested_writer.write_str(if is_referencable { r"\eqref{" } else { "(" } )?;
-        self.write_name(&mut nested_writer)?;
-        nested_writer.write_char(if is_referencable { '}' } else { ')' } )?;
-        Ok(unsafe { OW::from_raw_parts(nested_writer, features) })
-    }
+macro_rules! impl_for_label {
+    ($($lt:lifetime)?; $t:ty) => {
+        impl<$($lt,)? Fl, Fe, M, W, IW, OW> WriteAsLatex<Fl, Fe, Fe, M, M, W, IW, OW> for $t
+        where
+            Fl: SupportedFlavor,
+            Fe: LatexFeatures,
+            M: LatexMode,
+            W: Write,
+            IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = W>,
+            OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = W>,
+        {
+            fn write_as_latex(&self, dest: IW) -> Result<OW, Error> {
+                let (mut nested_writer, mut features) = dest.into_raw_parts();
+                if Fl::is_referencable(self) {
+                    features.require_package(Package::Amsmath);
+                    nested_writer.write_str(r"\eqref{")?;
+                    self.write_key(&mut nested_writer)?;
+                    nested_writer.write_char('}')?;
+                    features.record_reference(self);
+                } else {
+                    nested_writer.write_char('(')?;
+                    self.write_name(&mut nested_writer)?;
+                    nested_writer.write_char(')')?;
+                }
+                Ok(unsafe { OW::from_raw_parts(nested_writer, features) })
+            }
+        }
+    };
 }
+
+impl_for_label!(; CountersLabel);
+impl_for_label!('a; CustomLabel<'a>);
@@ -1,13 +1,29 @@
 use core::{fmt::Error, num::NonZeroU8};
 
-use crate::{latex_modes::DisplayMathMode, latex_writer::{LatexWriter, WriteLabel}, latex_flavors::{LatexFlavor, MathJax, AmsLatex}};
+use crate::{
+    buf::FixedCapacityBuffer,
+    latex_flavors::{AmsLatex, LatexFlavor, MathJax},
+    latex_modes::DisplayMathMode,
+    latex_writer::{LatexWriter, WriteLabel},
+};
 
 pub trait Label {
     fn is_subeq(&self) -> bool;
 
+    /// Writes the name that is displayed, e.g. `2a` in `\tag{2a}`
     fn write_name<W>(&self, dest: &mut W) -> Result<(), Error>
     where
         W: core::fmt::Write;
+
+    /// Writes the key that is used in `\label{...}` and `\eqref{...}`, e.g. `eq:2a`.
+    ///
+    /// By default, the key is the [name](Label::write_name).
+    fn write_key<W>(&self, dest: &mut W) -> Result<(), Error>
+    where
+        W: core::fmt::Write,
+    {
+        self.write_name(dest)
+    }
 }
 
 pub trait LabelGenerator {
@@ -15,6 +31,11 @@ pub trait LabelGenerator {
     type Change;
     type Error;
 
+    /// Applies the change to the counters and writes the label of the next equation.
+    ///
+    /// # Safety
+    ///
+    /// At most one label may be written per equation, i.e. per row of a multiline environment.
     unsafe fn write_next_label<W>(
         &mut self,
         dest: &mut W,
@@ -28,6 +49,92 @@ pub trait EqChangeExt: LabelGenerator {
     const EQ_CHANGE: Self::Change;
 }
 
+pub trait SubeqChangeExt: LabelGenerator {
+    /// The change that starts a new group of subequations, e.g. `2a` after `1`
+    const FIRST_SUBEQ_CHANGE: Self::Change;
+    /// The change that continues the group of subequations, e.g. `2b` after `2a`
+    const NEXT_SUBEQ_CHANGE: Self::Change;
+}
+
+/// A group of subequations that share the number of the equation, e.g. `2a`, `2b`, `2c`.
+///
+/// The first label of the group starts a new equation and the following ones continue it,
+/// like in the `subequations` environment of [`amsmath`](https://ctan.org/pkg/amsmath).
+/// Every label is written with `\tag` and `\label`, so the subequations are referencable
+/// with both [`AmsLatex`] and [`MathJax`], e.g. in the rows of
+/// [`AlignEnvironment`](crate::env::AlignEnvironment).
+///
+/// # Example
+///
+/// ```
+/// use nalgebra_latex::{
+///     env::{AlignEnvironment, LatexEnvironment},
+///     error::Error,
+///     fmt::labels::{Counters, Subequations},
+///     latex_features::NoFeatures,
+///     latex_flavors::MathJax,
+///     latex_modes::InnerParagraphMode,
+///     latex_writer::{LatexWriter, Writer},
+///     text::{RawLatex, WriteRawLatex},
+/// };
+///
+/// type W = Writer<MathJax, NoFeatures, InnerParagraphMode, String>;
+///
+/// let mut counters = Counters::new();
+/// let w = AlignEnvironment::with_env(W::default(), |mut w| {
+///     let mut subequations = Subequations::new(&mut counters);
+///     for (i, row) in [r"x&=1", r"y&=2"].into_iter().enumerate() {
+///         if i != 0 {
+///             w.write_raw(RawLatex::new(r"\\").unwrap())?;
+///         }
+///         w.write_raw(RawLatex::new(row).unwrap())?;
+///         unsafe { subequations.write_next_label(&mut w) }?;
+///     }
+///     Ok::<_, Error>(w)
+/// })
+/// .unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(
+///     s,
+///     r"\begin{align}x&=1\tag{1a}\label{1a}\\y&=2\tag{1b}\label{1b}\end{align}"
+/// );
+/// ```
+pub struct Subequations<'a, G> {
+    label_gen: &'a mut G,
+    is_first: bool,
+}
+
+impl<'a, G> Subequations<'a, G>
+where
+    G: SubeqChangeExt,
+{
+    pub fn new(label_gen: &'a mut G) -> Self {
+        Self {
+            label_gen,
+            is_first: true,
+        }
+    }
+
+    /// Writes the label of the next subequation of the group
+    ///
+    /// # Safety
+    ///
+    /// See [`LabelGenerator::write_next_label`].
+    pub unsafe fn write_next_label<W>(&mut self, dest: &mut W) -> Result<G::Label, G::Error>
+    where
+        W: LatexWriter<Mode = DisplayMathMode> + WriteLabel,
+    {
+        let change = if self.is_first {
+            G::FIRST_SUBEQ_CHANGE
+        } else {
+            G::NEXT_SUBEQ_CHANGE
+        };
+        let label = self.label_gen.write_next_label(dest, change)?;
+        self.is_first = false;
+        Ok(label)
+    }
+}
+
 #[derive(Debug)]
 pub struct LabelGenerationError;
 
@@ -37,6 +144,21 @@ pub enum CountersLabelGenerationError {
     FormattingError(Error),
 }
 
+impl From<LabelGenerationError> for crate::error::Error {
+    fn from(_e: LabelGenerationError) -> Self {
+        crate::error::Error::LabelExhaustion
+    }
+}
+
+impl From<CountersLabelGenerationError> for crate::error::Error {
+    fn from(e: CountersLabelGenerationError) -> Self {
+        match e {
+            CountersLabelGenerationError::LabelGenerationError => crate::error::Error::LabelExhaustion,
+            CountersLabelGenerationError::FormattingError(e) => crate::error::Error::Fmt(e),
+        }
+    }
+}
+
 pub struct Counters {
     equation: usize,
     subeq: Option<NonZeroU8>,
@@ -48,8 +170,17 @@ pub enum CountersChange {
     IncrementSubeq,
 }
 
-pub struct EquationLabel(String);
-pub struct SubeqLabel(String);
+/// The label of an equation, e.g. `2`
+pub struct EquationLabel {
+    equation: usize,
+}
+
+/// The label of a subequation, e.g. `2a`
+pub struct SubeqLabel {
+    equation: usize,
+    // 0 for `a`, 1 for `b`, etc.
+    subeq: u8,
+}
 
 pub enum CountersLabel {
     Equation(EquationLabel),
@@ -57,7 +188,9 @@ pub enum CountersLabel {
 }
 
 pub trait SupportedFlavor: LatexFlavor {
-    fn is_referencable(label: &CountersLabel) -> bool;
+    fn is_referencable<L>(label: &L) -> bool
+    where
+        L: Label;
 }
 
 impl Counters {
@@ -67,6 +200,17 @@ impl Counters {
             subeq: None,
         }
     }
+
+    /// Returns the builder of [`CustomCounters`], i.e. of counters with a configurable label scheme
+    pub const fn builder() -> CountersBuilder<'static> {
+        CountersBuilder::new()
+    }
+}
+
+impl Default for Counters {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl LabelGenerator for Counters {
@@ -88,8 +232,9 @@ impl LabelGenerator for Counters {
             CountersChange::IncrementEquation => {
                 self.equation = self.equation.checked_add(1).ok_or(LabelGenerationError)?;
                 self.subeq = None;
-                let label = format!("{}", self.equation);
-                CountersLabel::Equation(EquationLabel(label))
+                CountersLabel::Equation(EquationLabel {
+                    equation: self.equation,
+                })
             }
             CountersChange::IncrementSubeq => {
                 let n = match self.subeq {
@@ -99,15 +244,19 @@ impl LabelGenerator for Counters {
                 if n == 26 {
                     return Err(LabelGenerationError);
                 }
-                let label = format!("{}{}", self.equation, (b'a' + n) as char);
                 self.subeq = Some( NonZeroU8::new_unchecked(n + 1) );
-                CountersLabel::Subeq(SubeqLabel(label))
+                CountersLabel::Subeq(SubeqLabel {
+                    equation: self.equation,
+                    subeq: n,
+                })
             }
             CountersChange::IncrementEquationAndAddSubeq => {
                 self.equation = self.equation.checked_add(1).ok_or(LabelGenerationError)?;
                 self.subeq = Some( NonZeroU8::new_unchecked(1) );
-                let label = format!("{}a", self.equation);
-                CountersLabel::Subeq(SubeqLabel(label))
+                CountersLabel::Subeq(SubeqLabel {
+                    equation: self.equation,
+                    subeq: 0,
+                })
             }
         };
         dest.write_label(&label).map_err(FormattingError)?;
@@ -119,6 +268,11 @@ impl EqChangeExt for Counters {
     const EQ_CHANGE: Self::Change = CountersChange::IncrementEquation;
 }
 
+impl SubeqChangeExt for Counters {
+    const FIRST_SUBEQ_CHANGE: Self::Change = CountersChange::IncrementEquationAndAddSubeq;
+    const NEXT_SUBEQ_CHANGE: Self::Change = CountersChange::IncrementSubeq;
+}
+
 impl Label for CountersLabel {
     fn is_subeq(&self) -> bool {
         match self {
@@ -132,23 +286,472 @@ impl Label for CountersLabel {
         W: core::fmt::Write,
     {
         match self {
-            CountersLabel::Equation(label) => dest.write_str(label.0.as_str()),
-            CountersLabel::Subeq(label) => dest.write_str(label.0.as_str()),
+            CountersLabel::Equation(label) => write!(dest, "{}", label.equation),
+            CountersLabel::Subeq(label) => {
+                write!(dest, "{}{}", label.equation, (b'a' + label.subeq) as char)
+            }
         }
     }
 }
 
 impl SupportedFlavor for MathJax {
-    fn is_referencable(label: &CountersLabel) -> bool {
-        match label {
-            CountersLabel::Equation(_) => true,
-            CountersLabel::Subeq(_) => false,
-        }
+    fn is_referencable<L>(_label: &L) -> bool
+    where
+        L: Label,
+    {
+        true
     }
 }
 
 impl SupportedFlavor for AmsLatex {
-    fn is_referencable(_label: &CountersLabel) -> bool {
+    fn is_referencable<L>(_label: &L) -> bool
+    where
+        L: Label,
+    {
         true
     }
-}
\ No newline at end of file
+}
+
+/// The maximum number of section counters of [`CustomCounters`], e.g. 4 for `1.2.3.4.5`,
+/// where the last number is the equation
+pub const MAX_SECTION_DEPTH: usize = 4;
+
+/// The capacity of the name of [`CustomLabel`] in bytes
+pub const MAX_LABEL_NAME_LEN: usize = 32;
+
+/// The style of the numbers in the labels generated by [`CustomCounters`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumberStyle {
+    /// `1`, `2`, `3`, ...
+    Arabic,
+    /// `i`, `ii`, `iii`, ...
+    LowerRoman,
+    /// `I`, `II`, `III`, ...
+    UpperRoman,
+    /// `a`, `b`, ..., `z`, `aa`, `ab`, ...
+    LowerAlpha,
+    /// `A`, `B`, ..., `Z`, `AA`, `AB`, ...
+    UpperAlpha,
+}
+
+impl NumberStyle {
+    /// Writes the positive number `n` in the style. Writing zero fails unless the style is
+    /// [`NumberStyle::Arabic`].
+    pub fn write<W>(self, n: usize, dest: &mut W) -> Result<(), Error>
+    where
+        W: core::fmt::Write + ?Sized,
+    {
+        const ROMAN: [(usize, &str); 13] = [
+            (1000, "m"),
+            (900, "cm"),
+            (500, "d"),
+            (400, "cd"),
+            (100, "c"),
+            (90, "xc"),
+            (50, "l"),
+            (40, "xl"),
+            (10, "x"),
+            (9, "ix"),
+            (5, "v"),
+            (4, "iv"),
+            (1, "i"),
+        ];
+        let is_upper = matches!(self, NumberStyle::UpperRoman | NumberStyle::UpperAlpha);
+        let case = |b: u8| if is_upper { b.to_ascii_uppercase() } else { b } as char;
+        match self {
+            NumberStyle::Arabic => write!(dest, "{}", n),
+            _ if n == 0 => Err(Error),
+            NumberStyle::LowerRoman | NumberStyle::UpperRoman => {
+                let mut n = n;
+                for (value, numeral) in ROMAN {
+                    while n >= value {
+                        n -= value;
+                        for b in numeral.bytes() {
+                            dest.write_char(case(b))?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+            NumberStyle::LowerAlpha | NumberStyle::UpperAlpha => {
+                // Bijective base-26, i.e. `z` is followed by `aa`
+                let mut digits = [0u8; 14];
+                let mut len = 0;
+                let mut n = n;
+                while n > 0 {
+                    n -= 1;
+                    digits[len] = b'a' + (n % 26) as u8;
+                    len += 1;
+                    n /= 26;
+                }
+                for &b in digits[..len].iter().rev() {
+                    dest.write_char(case(b))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The numbers of a label generated by [`CustomCounters`], e.g. `[3, 2]`, `1` and `Some(2)`
+/// for `3.2.1b`
+#[derive(Clone, Copy, Debug)]
+pub struct LabelNumbers<'a> {
+    sections: &'a [usize],
+    equation: usize,
+    subeq: Option<usize>,
+}
+
+impl<'a> LabelNumbers<'a> {
+    /// The section counters, from the outermost to the innermost
+    pub fn sections(&self) -> &'a [usize] {
+        self.sections
+    }
+
+    /// The equation counter within the innermost section
+    pub fn equation(&self) -> usize {
+        self.equation
+    }
+
+    /// The one-based subequation counter, if the label is the label of a subequation
+    pub fn subeq(&self) -> Option<usize> {
+        self.subeq
+    }
+}
+
+/// The function pointer that writes the name of a label, which is the default
+/// type of the closure of [`CountersBuilder`]
+pub type LabelNameFn = fn(&LabelNumbers<'_>, &mut dyn core::fmt::Write) -> Result<(), Error>;
+
+/// The builder of [`CustomCounters`].
+///
+/// By default, the labels are the same as the ones of [`Counters`] except that subequations
+/// continue past `z` with `aa`, `ab`, etc.
+///
+/// # Example
+///
+/// ```
+/// use nalgebra_latex::{
+///     fmt::labels::{Counters, CountersChange, Label, LabelGenerator, NumberStyle},
+///     latex_features::NoFeatures,
+///     latex_flavors::AmsLatex,
+///     latex_modes::DisplayMathMode,
+///     latex_writer::{LatexWriter, Writer},
+/// };
+///
+/// type W = Writer<AmsLatex, NoFeatures, DisplayMathMode, String>;
+///
+/// let mut counters = Counters::builder()
+///     .with_prefix("eq:")
+///     .with_section_depth(2)
+///     .with_subeq_style(NumberStyle::UpperAlpha)
+///     .build();
+/// counters.advance_section(0).unwrap();
+/// counters.advance_section(1).unwrap();
+/// counters.advance_section(1).unwrap();
+///
+/// let mut w = W::default();
+/// unsafe { counters.write_next_label(&mut w, CountersChange::IncrementEquation) }.unwrap();
+/// unsafe { counters.write_next_label(&mut w, CountersChange::IncrementEquationAndAddSubeq) }
+///     .unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(s, r"\tag{1.2.1}\label{eq:1.2.1}\tag{1.2.2A}\label{eq:1.2.2A}");
+///
+/// // Subequations continue past `z`
+/// let mut counters = Counters::builder().with_equation_style(NumberStyle::LowerRoman).build();
+/// let mut w = W::default();
+/// unsafe { counters.write_next_label(&mut w, CountersChange::IncrementEquationAndAddSubeq) }
+///     .unwrap();
+/// let mut w = W::default();
+/// for _ in 0..26 {
+///     unsafe { counters.write_next_label(&mut w, CountersChange::IncrementSubeq) }.unwrap();
+/// }
+/// let label = unsafe { counters.write_next_label(&mut w, CountersChange::IncrementSubeq) }
+///     .unwrap();
+/// assert_eq!(label.name(), "iab");
+///
+/// // The names can be written with a closure
+/// let mut counters = Counters::builder()
+///     .with_names(|numbers, dest| write!(dest, "E{}", numbers.equation() * 10))
+///     .build();
+/// let mut w = W::default();
+/// let label = unsafe { counters.write_next_label(&mut w, CountersChange::IncrementEquation) }
+///     .unwrap();
+/// assert_eq!(label.name(), "E10");
+/// ```
+#[derive(Clone, Copy)]
+pub struct CountersBuilder<'a, F = LabelNameFn> {
+    prefix: &'a str,
+    separator: &'a str,
+    section_depth: usize,
+    equation_style: NumberStyle,
+    subeq_style: NumberStyle,
+    names: Option<F>,
+}
+
+impl CountersBuilder<'static> {
+    /// Creates the builder of the default scheme, i.e. arabic equation numbers without sections
+    /// and lowercase letters for subequations, e.g. `2a`
+    pub const fn new() -> Self {
+        Self {
+            prefix: "",
+            separator: ".",
+            section_depth: 0,
+            equation_style: NumberStyle::Arabic,
+            subeq_style: NumberStyle::LowerAlpha,
+            names: None,
+        }
+    }
+}
+
+impl Default for CountersBuilder<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, F> CountersBuilder<'a, F>
+where
+    F: FnMut(&LabelNumbers<'_>, &mut dyn core::fmt::Write) -> Result<(), Error>,
+{
+    /// Sets the prefix of the keys of the labels, e.g. `eq:`. The prefix is not displayed.
+    pub fn with_prefix<'b>(self, prefix: &'b str) -> CountersBuilder<'b, F>
+    where
+        'a: 'b,
+    {
+        CountersBuilder {
+            prefix,
+            separator: self.separator,
+            section_depth: self.section_depth,
+            equation_style: self.equation_style,
+            subeq_style: self.subeq_style,
+            names: self.names,
+        }
+    }
+
+    /// Sets the separator between the section counters and the equation counter, `.` by default
+    pub fn with_separator<'b>(self, separator: &'b str) -> CountersBuilder<'b, F>
+    where
+        'a: 'b,
+    {
+        CountersBuilder {
+            prefix: self.prefix,
+            separator,
+            section_depth: self.section_depth,
+            equation_style: self.equation_style,
+            subeq_style: self.subeq_style,
+            names: self.names,
+        }
+    }
+
+    /// Sets the number of section counters that precede the equation counter, e.g. 2 for `3.2.1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` exceeds [`MAX_SECTION_DEPTH`].
+    pub fn with_section_depth(self, depth: usize) -> Self {
+        assert!(depth <= MAX_SECTION_DEPTH, "the section depth exceeds MAX_SECTION_DEPTH");
+        Self {
+            section_depth: depth,
+            ..self
+        }
+    }
+
+    /// Sets the style of the equation counter, [`NumberStyle::Arabic`] by default
+    pub fn with_equation_style(self, style: NumberStyle) -> Self {
+        Self {
+            equation_style: style,
+            ..self
+        }
+    }
+
+    /// Sets the style of the subequation counter, [`NumberStyle::LowerAlpha`] by default
+    pub fn with_subeq_style(self, style: NumberStyle) -> Self {
+        Self {
+            subeq_style: style,
+            ..self
+        }
+    }
+
+    /// Sets the closure that writes the names of the labels instead of the number styles
+    /// and the separator. The names must fit in [`MAX_LABEL_NAME_LEN`] bytes.
+    pub fn with_names<G>(self, names: G) -> CountersBuilder<'a, G>
+    where
+        G: FnMut(&LabelNumbers<'_>, &mut dyn core::fmt::Write) -> Result<(), Error>,
+    {
+        CountersBuilder {
+            prefix: self.prefix,
+            separator: self.separator,
+            section_depth: self.section_depth,
+            equation_style: self.equation_style,
+            subeq_style: self.subeq_style,
+            names: Some(names),
+        }
+    }
+
+    /// Creates the label generator with the scheme and all counters set to zero
+    pub fn build(self) -> CustomCounters<'a, F> {
+        CustomCounters {
+            scheme: self,
+            sections: [0; MAX_SECTION_DEPTH],
+            equation: 0,
+            subeq: None,
+        }
+    }
+}
+
+/// The label generator with a configurable label scheme, see [`CountersBuilder`]
+pub struct CustomCounters<'a, F = LabelNameFn> {
+    scheme: CountersBuilder<'a, F>,
+    sections: [usize; MAX_SECTION_DEPTH],
+    equation: usize,
+    subeq: Option<usize>,
+}
+
+impl<'a, F> CustomCounters<'a, F>
+where
+    F: FnMut(&LabelNumbers<'_>, &mut dyn core::fmt::Write) -> Result<(), Error>,
+{
+    /// Increments the section counter at the zero-based `level` and resets the deeper
+    /// section counters and the equation counter, e.g. `\section` is level 0.
+    pub fn advance_section(&mut self, level: usize) -> Result<(), LabelGenerationError> {
+        if level >= self.scheme.section_depth {
+            return Err(LabelGenerationError);
+        }
+        self.sections[level] = self.sections[level]
+            .checked_add(1)
+            .ok_or(LabelGenerationError)?;
+        for section in &mut self.sections[level + 1..] {
+            *section = 0;
+        }
+        self.equation = 0;
+        self.subeq = None;
+        Ok(())
+    }
+
+    fn write_name(
+        &mut self,
+        numbers: &LabelNumbers<'_>,
+        dest: &mut dyn core::fmt::Write,
+    ) -> Result<(), Error> {
+        let scheme = &mut self.scheme;
+        if let Some(names) = &mut scheme.names {
+            return names(numbers, dest);
+        }
+        for section in numbers.sections {
+            write!(dest, "{}", section)?;
+            dest.write_str(scheme.separator)?;
+        }
+        scheme.equation_style.write(numbers.equation, dest)?;
+        if let Some(subeq) = numbers.subeq {
+            scheme.subeq_style.write(subeq, dest)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, F> LabelGenerator for CustomCounters<'a, F>
+where
+    F: FnMut(&LabelNumbers<'_>, &mut dyn core::fmt::Write) -> Result<(), Error>,
+{
+    type Change = CountersChange;
+    type Error = CountersLabelGenerationError;
+    type Label = CustomLabel<'a>;
+
+    unsafe fn write_next_label<W>(
+        &mut self,
+        dest: &mut W,
+        c: Self::Change,
+    ) -> Result<Self::Label, CountersLabelGenerationError>
+    where
+        W: LatexWriter<Mode = DisplayMathMode> + WriteLabel,
+    {
+        use CountersLabelGenerationError::*;
+
+        match c {
+            CountersChange::IncrementEquation => {
+                self.equation = self.equation.checked_add(1).ok_or(LabelGenerationError)?;
+                self.subeq = None;
+            }
+            CountersChange::IncrementSubeq => {
+                let n = self.subeq.ok_or(LabelGenerationError)?;
+                self.subeq = Some(n.checked_add(1).ok_or(LabelGenerationError)?);
+            }
+            CountersChange::IncrementEquationAndAddSubeq => {
+                self.equation = self.equation.checked_add(1).ok_or(LabelGenerationError)?;
+                self.subeq = Some(1);
+            }
+        };
+        let sections = self.sections;
+        let numbers = LabelNumbers {
+            sections: &sections[..self.scheme.section_depth],
+            equation: self.equation,
+            subeq: self.subeq,
+        };
+        let mut name = FixedCapacityBuffer::new();
+        self.write_name(&numbers, &mut name).map_err(FormattingError)?;
+        let label = CustomLabel {
+            prefix: self.scheme.prefix,
+            name,
+            is_subeq: self.subeq.is_some(),
+        };
+        dest.write_label(&label).map_err(FormattingError)?;
+        Ok(label)
+    }
+}
+
+impl<'a, F> EqChangeExt for CustomCounters<'a, F>
+where
+    F: FnMut(&LabelNumbers<'_>, &mut dyn core::fmt::Write) -> Result<(), Error>,
+{
+    const EQ_CHANGE: Self::Change = CountersChange::IncrementEquation;
+}
+
+impl<'a, F> SubeqChangeExt for CustomCounters<'a, F>
+where
+    F: FnMut(&LabelNumbers<'_>, &mut dyn core::fmt::Write) -> Result<(), Error>,
+{
+    const FIRST_SUBEQ_CHANGE: Self::Change = CountersChange::IncrementEquationAndAddSubeq;
+    const NEXT_SUBEQ_CHANGE: Self::Change = CountersChange::IncrementSubeq;
+}
+
+/// The label generated by [`CustomCounters`]
+#[derive(Clone, Copy, Debug)]
+pub struct CustomLabel<'a> {
+    prefix: &'a str,
+    name: FixedCapacityBuffer<MAX_LABEL_NAME_LEN>,
+    is_subeq: bool,
+}
+
+impl<'a> CustomLabel<'a> {
+    /// The displayed name, e.g. `1.2.3a`
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// The prefix of the key, e.g. `eq:`
+    pub fn prefix(&self) -> &'a str {
+        self.prefix
+    }
+}
+
+impl<'a> Label for CustomLabel<'a> {
+    fn is_subeq(&self) -> bool {
+        self.is_subeq
+    }
+
+    fn write_name<W>(&self, dest: &mut W) -> Result<(), Error>
+    where
+        W: core::fmt::Write,
+    {
+        dest.write_str(self.name.as_str())
+    }
+
+    fn write_key<W>(&self, dest: &mut W) -> Result<(), Error>
+    where
+        W: core::fmt::Write,
+    {
+        dest.write_str(self.prefix)?;
+        dest.write_str(self.name.as_str())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    error::Error,
     latex_features::LatexFeatures,
     latex_flavors::LatexFlavor,
     latex_modes::{DisplayMathMode, InnerParagraphMode, LatexMode},
@@ -9,10 +10,17 @@ use crate::{
 
 use self::labels::{EqChangeExt, LabelGenerator};
 
-#[cfg(feature = "lin_sys")]
+pub mod coefficient;
 pub mod labels;
+pub mod number;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod references;
 
+mod impl_complex;
 mod impl_consuming_write_as_latex;
+#[cfg(feature = "rational")]
+mod impl_num_rational;
 mod impl_partial_endofunctional_write_as_latex;
 mod impl_write_as_latex;
 
@@ -30,7 +38,7 @@ pub trait LatexFormatter<
     InitialMode: LatexMode,
     ConsequentMode: LatexMode,
 {
-    fn fmt<IW, OW>(dest: IW, input: &I) -> Result<OW, core::fmt::Error>
+    fn fmt<IW, OW>(dest: IW, input: &I) -> Result<OW, Error>
     where
         IW: LatexWriter<
             Flavor = Flavor,
@@ -52,7 +60,7 @@ where
     IW: LatexWriter<Flavor = Fl, Features = InitFe, Mode = InitM, NestedWriter = NestedWriter>,
     OW: LatexWriter<Flavor = Fl, Features = ConseqFe, Mode = ConseqM, NestedWriter = NestedWriter>,
 {
-    fn write_as_latex(&self, dest: IW) -> Result<OW, core::fmt::Error>;
+    fn write_as_latex(&self, dest: IW) -> Result<OW, Error>;
 }
 
 pub trait ConsumingWriteAsLatex<Fl, InitFe, ConseqFe, InitM, ConseqM, NestedWriter, IW, OW>
@@ -66,7 +74,7 @@ where
     IW: LatexWriter<Flavor = Fl, Features = InitFe, Mode = InitM, NestedWriter = NestedWriter>,
     OW: LatexWriter<Flavor = Fl, Features = ConseqFe, Mode = ConseqM, NestedWriter = NestedWriter>,
 {
-    fn consuming_write_as_latex(self, dest: IW) -> Result<OW, core::fmt::Error>;
+    fn consuming_write_as_latex(self, dest: IW) -> Result<OW, Error>;
 }
 
 // The function is endofunctional in the second argument in a sense that
@@ -79,7 +87,7 @@ where
     Fe: LatexFeatures,
     M: LatexMode,
 {
-    fn partial_endofunctional_write_as_latex<W, NW>(&self, dest: W) -> Result<W, core::fmt::Error>
+    fn partial_endofunctional_write_as_latex<W, NW>(&self, dest: W) -> Result<W, Error>
     where
         NW: core::fmt::Write,
         W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NW>;
@@ -91,21 +99,21 @@ where
     Fl: LatexFlavor,
     Fe: LatexFeatures,
 {
-    #[allow(deprecated)]
     fn format_as_labelled_display_math_block<G, IW, OW, L>(
         dest: IW,
         label_gen: &mut G,
         input: &I,
-    ) -> Result<(OW,L), core::fmt::Error>
+    ) -> Result<(OW,L), Error>
     where
         G: LabelGenerator<Label = L> + EqChangeExt,
+        Error: From<G::Error>,
         IW: LatexWriter<
                 Flavor = Fl,
                 Features = Fe,
                 Mode = InnerParagraphMode,
                 NestedWriter = OW::NestedWriter,
-            > + WriteTwoDollarSignsTargetExt,
-        IW::WriteTwoDollarSignsTarget: LatexWriter<Mode = DisplayMathMode>,
-        IW::DisplayMathWriter: WriteTwoDollarSignsTargetExt<WriteTwoDollarSignsTarget = IW> + WriteLabel,
+            > + WriteTwoDollarSignsTargetExt<TwoDollarSignsTargetMode = DisplayMathMode>,
+        IW::WithMode<DisplayMathMode>:
+            WriteTwoDollarSignsTargetExt<TwoDollarSignsTargetMode = InnerParagraphMode> + WriteLabel,
         OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = InnerParagraphMode>;
 }
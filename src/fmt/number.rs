@@ -0,0 +1,810 @@
+//! Module with the formatting policy of floating-point numbers.
+//!
+//! By default, [`f32`] and [`f64`] are written with [`Display`], which can produce e.g.
+//! `0.00000000000000000001` for `1e-20`. A [`NumberFormat`] chooses another [`Notation`]
+//! and can delegate the typesetting to `\num{}` of [`siunitx`]. Regardless of the format,
+//! infinities are written as `\infty` and `-\infty`, and NaN as `\text{NaN}`. The policy
+//! produces math mode [LaTeX], save for `\num{}`, which also works in text modes.
+//! Outside of math mode, the numbers that need math mode are therefore enclosed in `$`.
+//!
+//! The format can be attached
+//!
+//! * to a writer with [`Writer::with_number_format`], or
+//! * to a formatter with [`NumberFormatted`],
+//!
+//! and applies to every number written by the matrix and linear-system formatters,
+//! including the parts of [`Complex`] numbers, whose form is chosen with [`ComplexForm`].
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra::matrix;
+//! use nalgebra_latex::{
+//!     fmt::{
+//!         number::{NumberFormat, NumberFormatPolicy, NumberFormatted},
+//!         LatexFormatter,
+//!     },
+//!     latex_features::{NoFeatures, RequiredPackages, Package},
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::DisplayMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//!     matrix::fmt::PlainMatrixFormatter,
+//! };
+//!
+//! let m = matrix![1e-20, f64::INFINITY; 1234.5, f64::NAN];
+//!
+//! let w = Writer::<AmsLatex, NoFeatures, DisplayMathMode, String>::default()
+//!     .with_number_format(NumberFormat::scientific(2));
+//! let w: Writer<_, _, DisplayMathMode, _> = PlainMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{matrix}1.0 \times 10^{-20}&\infty\\1.2 \times 10^{3}&\text{NaN}\end{matrix}"
+//! );
+//!
+//! struct Siunitx;
+//!
+//! impl NumberFormatPolicy for Siunitx {
+//!     const FORMAT: NumberFormat = NumberFormat::engineering(3).with_siunitx();
+//! }
+//!
+//! type W = Writer<AmsLatex, RequiredPackages, DisplayMathMode, String>;
+//!
+//! let w: W = Writer::<AmsLatex, NoFeatures, DisplayMathMode, String>::default().track_packages();
+//! let w: W = NumberFormatted::<PlainMatrixFormatter, Siunitx>::fmt(w, &m).unwrap();
+//! assert!(w.required_packages().contains(Package::Siunitx));
+//! let (s, _required_packages) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{matrix}\num{10.0e-21}&\infty\\\num{1.23e3}&\text{NaN}\end{matrix}"
+//! );
+//!
+//! # #[cfg(feature = "lin_sys")]
+//! # {
+//! use nalgebra::Const;
+//! use nalgebra_latex::lin_sys::{
+//!     fmt::PlainLinSysFormatter,
+//!     numbering::Numbering,
+//!     unknowns::SingleLetterBoldfaceVecOfUnknowns,
+//!     LinSys,
+//! };
+//!
+//! let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<2>);
+//! let lin_sys = LinSys::new(matrix![0.5, -2.0, 1.0 / 3.0], unknowns).unwrap();
+//! let w = Writer::<AmsLatex, NoFeatures, DisplayMathMode, String>::default()
+//!     .with_number_format(NumberFormat::fixed_precision(2));
+//! let w: Writer<_, _, DisplayMathMode, _> = PlainLinSysFormatter::fmt(w, &lin_sys).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(s, r"0.50x_{1}+-2.00x_{2}=0.33");
+//! # }
+//! ```
+//!
+//! In a paragraph, only the numbers that need math mode are enclosed in `$`:
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     fmt::{number::NumberFormat, WriteAsLatex},
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::InnerParagraphMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! type W = Writer<AmsLatex, NoFeatures, InnerParagraphMode, String>;
+//!
+//! let w = W::default().with_number_format(NumberFormat::scientific(2));
+//! let w: Writer<_, _, InnerParagraphMode, _> = 1e-20.write_as_latex(w).unwrap();
+//! let w: Writer<_, _, InnerParagraphMode, _> = f64::INFINITY.write_as_latex(w).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(s, r"$1.0 \times 10^{-20}$$\infty$");
+//!
+//! let w = W::default().with_number_format(NumberFormat::fixed_precision(2));
+//! let w: Writer<_, _, InnerParagraphMode, _> = 0.5.write_as_latex(w).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(s, "0.50");
+//! ```
+//!
+//!
+//! # Fractions
+//!
+//! With the `rational` feature, [`Ratio`] from [`num-rational`] is written as a fraction
+//! in the [`FractionStyle`] of the format. Floats can be approximated by fractions with
+//! [`Notation::NearestFraction`].
+//!
+//! ```
+//! use nalgebra::matrix;
+//! use nalgebra_latex::{
+//!     fmt::{
+//!         number::{FractionStyle, NumberFormat},
+//!         LatexFormatter,
+//!     },
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::InlineMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//!     matrix::fmt::BracketedMatrixFormatter,
+//! };
+//!
+//! type W<Fe> = Writer<AmsLatex, Fe, InlineMathMode, String>;
+//!
+//! let m = matrix![0.75, -1.0 / 3.0; 3.14159265, 2.0];
+//! let w = W::<NoFeatures>::default()
+//!     .with_number_format(NumberFormat::nearest_fraction(1000).with_fraction_style(FractionStyle::Tfrac));
+//! let w: W<_> = BracketedMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{bmatrix}\tfrac{3}{4}&-\tfrac{1}{3}\\\tfrac{355}{113}&2\end{bmatrix}"
+//! );
+//!
+//! # #[cfg(feature = "rational")]
+//! # {
+//! use num_rational::Rational64;
+//!
+//! let m = matrix![Rational64::new(3, 4), Rational64::new(-1, 2); Rational64::new(-7, 2), Rational64::from(2)];
+//! let w = W::<NoFeatures>::default();
+//! let w: W<_> = BracketedMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{bmatrix}\frac{3}{4}&-\frac{1}{2}\\-\frac{7}{2}&2\end{bmatrix}"
+//! );
+//!
+//! let w = W::<NoFeatures>::default()
+//!     .with_number_format(NumberFormat::DEFAULT.with_fraction_style(FractionStyle::Mixed));
+//! let w: W<_> = BracketedMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{bmatrix}\frac{3}{4}&-\frac{1}{2}\\-3\frac{1}{2}&2\end{bmatrix}"
+//! );
+//!
+//! let w = W::<NoFeatures>::default()
+//!     .with_number_format(NumberFormat::DEFAULT.with_fraction_style(FractionStyle::Slash));
+//! let w: W<_> = BracketedMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(s, r"\begin{bmatrix}3/4&-1/2\\-7/2&2\end{bmatrix}");
+//!
+//! // In a paragraph, only the fractions written with commands are enclosed in `$`
+//! use nalgebra_latex::{fmt::WriteAsLatex, latex_modes::InnerParagraphMode};
+//!
+//! type P = Writer<AmsLatex, NoFeatures, InnerParagraphMode, String>;
+//!
+//! let w: P = Rational64::new(3, 4).write_as_latex(P::default()).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(s, r"$\frac{3}{4}$");
+//!
+//! let w = P::default()
+//!     .with_number_format(NumberFormat::DEFAULT.with_fraction_style(FractionStyle::Slash));
+//! let w: Writer<_, _, InnerParagraphMode, _> = Rational64::new(3, 4).write_as_latex(w).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(s, "3/4");
+//! # }
+//! ```
+//!
+//! [`Ratio`]: https://docs.rs/num-rational/latest/num_rational/struct.Ratio.html
+//! [`num-rational`]: https://crates.io/crates/num-rational
+//!
+//! # Complex numbers
+//!
+//! ```
+//! use nalgebra::{matrix, Complex};
+//! use nalgebra_latex::{
+//!     fmt::{
+//!         number::{ComplexForm, ImaginaryUnit, NumberFormat},
+//!         LatexFormatter,
+//!     },
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::InlineMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//!     matrix::fmt::ParenthesizedMatrixFormatter,
+//! };
+//!
+//! type W<Fe> = Writer<AmsLatex, Fe, InlineMathMode, String>;
+//!
+//! let m = matrix![
+//!     Complex::new(1.0, 2.0), Complex::new(1.5, -1.0);
+//!     Complex::new(0.0, -0.5), Complex::new(-3.0, 0.0)
+//! ];
+//! let w: W<_> = ParenthesizedMatrixFormatter::fmt(W::<NoFeatures>::default(), &m).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"\begin{pmatrix}1+2i&1.5-i\\-0.5i&-3\end{pmatrix}");
+//!
+//! let w = W::<NoFeatures>::default()
+//!     .with_number_format(NumberFormat::DEFAULT.with_imaginary_unit(ImaginaryUnit::J));
+//! let w: W<_> = ParenthesizedMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(s, r"\begin{pmatrix}1+2j&1.5-j\\-0.5j&-3\end{pmatrix}");
+//!
+//! // The polar form requires the `std` feature
+//! # #[cfg(feature = "std")]
+//! # {
+//! let w = W::<NoFeatures>::default()
+//!     .with_number_format(NumberFormat::fixed_precision(2).with_complex_form(ComplexForm::Polar));
+//! let w: W<_> = ParenthesizedMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{pmatrix}2.24e^{i1.11}&1.80e^{-i0.59}\\0.50e^{-i1.57}&3.00e^{i3.14}\end{pmatrix}"
+//! );
+//!
+//! // In a paragraph, the polar form is enclosed in `$`
+//! use nalgebra_latex::{fmt::WriteAsLatex, latex_modes::InnerParagraphMode};
+//!
+//! let w = Writer::<AmsLatex, NoFeatures, InnerParagraphMode, String>::default()
+//!     .with_number_format(NumberFormat::fixed_precision(2).with_complex_form(ComplexForm::Polar));
+//! let w: Writer<_, _, InnerParagraphMode, _> = Complex::new(0.0, -0.5).write_as_latex(w).unwrap();
+//! let (s, _features) = w.into_raw_parts();
+//! assert_eq!(s, r"$0.50e^{-i1.57}$");
+//! # }
+//! ```
+//!
+//! [`Complex`]: nalgebra::Complex
+//! [`Display`]: core::fmt::Display
+//! [`siunitx`]: https://ctan.org/pkg/siunitx
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+//! [`Writer::with_number_format`]: crate::latex_writer::Writer::with_number_format
+
+use core::{
+    fmt::{Display, LowerExp, Write},
+    marker::PhantomData,
+};
+
+use super::{coefficient::Coefficient, LatexFormatter};
+use crate::{
+    buf::FixedCapacityBuffer,
+    error::Error,
+    latex_features::{LatexFeatures, Package, WithNumberFormat},
+    latex_flavors::{LatexFlavor, LatexFlavorKind, LatexFlavorKindExt},
+    latex_modes::LatexMode,
+    latex_writer::LatexWriter,
+};
+
+/// The greatest number of digits that [`Notation`]s accept, larger numbers are clamped
+pub const MAX_DIGITS: usize = 40;
+
+/// The notation of floating-point numbers
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Notation {
+    /// The notation of [`Display`], i.e. the shortest decimal that
+    /// round-trips
+    Display,
+    /// The fixed number of digits after the decimal point, e.g. `0.50` for 2
+    FixedPrecision(usize),
+    /// The fixed number of significant digits, e.g. `0.0123` for 3. Very small and very large
+    /// numbers are written in the scientific notation, like with `%g` in C.
+    SignificantDigits(usize),
+    /// The scientific notation with the number of significant digits,
+    /// e.g. `1.2 \times 10^{-20}` for 2. The exponent is omitted when it is zero.
+    Scientific(usize),
+    /// The engineering notation with the number of significant digits, i.e. the scientific
+    /// notation where the exponent is a multiple of 3, e.g. `12 \times 10^{-21}` for 2
+    Engineering(usize),
+    /// The nearest fraction with the denominator not exceeding the number, e.g. `\frac{1}{3}`
+    /// for `0.333` and 10. The fraction is written in the [`FractionStyle`] of the format.
+    /// Numbers that are too large for [`u64`] are written like with [`Notation::Display`].
+    NearestFraction(u64),
+}
+
+/// The style of fractions, e.g. of rational numbers
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FractionStyle {
+    /// `\frac{3}{4}`, or `{3\over 4}` in plain TeX
+    Frac,
+    /// `\dfrac{3}{4}` of [`amsmath`](https://ctan.org/pkg/amsmath), i.e. a display-style fraction
+    Dfrac,
+    /// `\tfrac{3}{4}` of [`amsmath`](https://ctan.org/pkg/amsmath), i.e. a text-style fraction
+    Tfrac,
+    /// `3/4`
+    Slash,
+    /// The mixed number with the fractional part written as [`FractionStyle::Frac`],
+    /// e.g. `1\frac{1}{2}` for `3/2`
+    Mixed,
+}
+
+/// The imaginary unit of complex numbers
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImaginaryUnit {
+    /// `i`, the convention of mathematics and physics
+    I,
+    /// `j`, the convention of electrical engineering
+    J,
+}
+
+/// The form of complex numbers
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ComplexForm {
+    /// `a+bi`
+    Cartesian,
+    /// `re^{i\theta}` with `\theta` in radians, in `(-\pi, \pi]`.
+    ///
+    /// Computing the polar form requires the `std` feature. Without it, writing a complex
+    /// number in the polar form fails with [`Error::InvalidInput`].
+    Polar,
+}
+
+/// The formatting policy of numbers, see the [module-level documentation](self).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NumberFormat {
+    notation: Notation,
+    siunitx: bool,
+    fraction_style: FractionStyle,
+    imaginary_unit: ImaginaryUnit,
+    complex_form: ComplexForm,
+}
+
+/// Implementors are type-level [`NumberFormat`]s for [`NumberFormatted`].
+pub trait NumberFormatPolicy {
+    const FORMAT: NumberFormat;
+}
+
+/// Formatter that formats the input with `F` using the number format of `P`.
+pub struct NumberFormatted<F, P>(PhantomData<(F, P)>);
+
+impl NumberFormat {
+    pub const DEFAULT: Self = Self::new(Notation::Display);
+
+    pub const fn new(notation: Notation) -> Self {
+        Self {
+            notation,
+            siunitx: false,
+            fraction_style: FractionStyle::Frac,
+            imaginary_unit: ImaginaryUnit::I,
+            complex_form: ComplexForm::Cartesian,
+        }
+    }
+
+    pub const fn fixed_precision(digits: usize) -> Self {
+        Self::new(Notation::FixedPrecision(digits))
+    }
+
+    pub const fn significant_digits(digits: usize) -> Self {
+        Self::new(Notation::SignificantDigits(digits))
+    }
+
+    pub const fn scientific(digits: usize) -> Self {
+        Self::new(Notation::Scientific(digits))
+    }
+
+    pub const fn engineering(digits: usize) -> Self {
+        Self::new(Notation::Engineering(digits))
+    }
+
+    pub const fn nearest_fraction(max_denominator: u64) -> Self {
+        Self::new(Notation::NearestFraction(max_denominator))
+    }
+
+    /// Makes the format write finite numbers as `\num{}` of [`siunitx`](https://ctan.org/pkg/siunitx),
+    /// e.g. `\num{1.2e-20}` instead of `1.2 \times 10^{-20}`. Fractions are not affected.
+    pub const fn with_siunitx(self) -> Self {
+        Self {
+            siunitx: true,
+            ..self
+        }
+    }
+
+    pub const fn with_fraction_style(self, fraction_style: FractionStyle) -> Self {
+        Self {
+            fraction_style,
+            ..self
+        }
+    }
+
+    pub const fn with_imaginary_unit(self, imaginary_unit: ImaginaryUnit) -> Self {
+        Self {
+            imaginary_unit,
+            ..self
+        }
+    }
+
+    pub const fn with_complex_form(self, complex_form: ComplexForm) -> Self {
+        Self {
+            complex_form,
+            ..self
+        }
+    }
+
+    pub const fn notation(&self) -> Notation {
+        self.notation
+    }
+
+    pub const fn uses_siunitx(&self) -> bool {
+        self.siunitx
+    }
+
+    pub const fn fraction_style(&self) -> FractionStyle {
+        self.fraction_style
+    }
+
+    pub const fn imaginary_unit(&self) -> ImaginaryUnit {
+        self.imaginary_unit
+    }
+
+    pub const fn complex_form(&self) -> ComplexForm {
+        self.complex_form
+    }
+
+    /// Checks whether the float is written without math mode commands, i.e. whether it can be
+    /// written as is outside of math mode
+    pub(crate) fn writes_text<T>(&self, x: T) -> bool
+    where
+        T: Float,
+    {
+        !x.is_nan()
+            && !x.is_infinite()
+            && (self.siunitx
+                || matches!(self.notation, Notation::Display | Notation::FixedPrecision(_)))
+    }
+
+    /// Writes the number in the format, recording the required packages in `features`.
+    pub fn write_float<Fl, T, W, Fe>(
+        &self,
+        dest: &mut W,
+        features: &mut Fe,
+        x: T,
+    ) -> Result<(), Error>
+    where
+        Fl: LatexFlavorKindExt,
+        T: Float,
+        W: Write,
+        Fe: LatexFeatures,
+    {
+        if x.is_nan() {
+            return if let LatexFlavorKind::PlainTex = Fl::KIND {
+                dest.write_str(r"\mathrm{NaN}").map_err(Error::from)
+            } else {
+                features.require_package(Package::Amsmath);
+                dest.write_str(r"\text{NaN}").map_err(Error::from)
+            };
+        }
+        if x.is_infinite() {
+            return dest
+                .write_str(if x.is_sign_negative() {
+                    r"-\infty"
+                } else {
+                    r"\infty"
+                })
+                .map_err(Error::from);
+        }
+        if let Notation::NearestFraction(max_denominator) = self.notation {
+            if let Some((numer, denom)) = nearest_fraction(x.to_f64(), max_denominator) {
+                return self.write_fraction_of_u64s::<Fl, _, _>(
+                    dest,
+                    features,
+                    x.is_sign_negative(),
+                    numer,
+                    denom,
+                );
+            }
+        }
+        if self.siunitx {
+            features.require_package(Package::Siunitx);
+            dest.write_str(r"\num{")?;
+        }
+        match self.notation {
+            Notation::Display => write!(dest, "{}", x)?,
+            Notation::FixedPrecision(digits) => write!(dest, "{:.*}", digits, x)?,
+            Notation::SignificantDigits(digits) => {
+                let sci = Scientific::new(x, digits)?;
+                if sci.exp < -4 || sci.exp >= sci.digits().len() as i32 {
+                    sci.write(dest, 0, self.siunitx)?;
+                } else {
+                    sci.write_fixed(dest)?;
+                }
+            }
+            Notation::Scientific(digits) => {
+                Scientific::new(x, digits)?.write(dest, 0, self.siunitx)?
+            }
+            Notation::Engineering(digits) => {
+                let sci = Scientific::new(x, digits)?;
+                sci.write(dest, sci.exp.rem_euclid(3) as usize, self.siunitx)?;
+            }
+            Notation::NearestFraction(_) => write!(dest, "{}", x)?,
+        }
+        if self.siunitx {
+            dest.write_char('}')?;
+        }
+        Ok(())
+    }
+
+    fn write_fraction_of_u64s<Fl, W, Fe>(
+        &self,
+        dest: &mut W,
+        features: &mut Fe,
+        is_negative: bool,
+        numer: u64,
+        denom: u64,
+    ) -> Result<(), Error>
+    where
+        Fl: LatexFlavorKindExt,
+        W: Write,
+        Fe: LatexFeatures,
+    {
+        let is_negative = is_negative && numer != 0;
+        if denom == 1 || numer == 0 {
+            if is_negative {
+                dest.write_char('-')?;
+            }
+            return write!(dest, "{}", numer).map_err(Error::from);
+        }
+        let (whole, numer) = match self.fraction_style {
+            FractionStyle::Mixed if numer >= denom => (Some(numer / denom), numer % denom),
+            _ => (None, numer),
+        };
+        self.write_fraction::<Fl, _, _, _>(dest, features, is_negative, whole, numer, denom)
+    }
+
+    /// Writes `-` if `is_negative`, then `whole` if any and then the fraction of the
+    /// non-negative `numer` and `denom` in the [`FractionStyle`] of the format.
+    ///
+    /// Computing `whole` for [`FractionStyle::Mixed`], as well as writing the integers
+    /// without the fraction, is up to the caller.
+    pub fn write_fraction<Fl, W, Fe, T>(
+        &self,
+        dest: &mut W,
+        features: &mut Fe,
+        is_negative: bool,
+        whole: Option<T>,
+        numer: T,
+        denom: T,
+    ) -> Result<(), Error>
+    where
+        Fl: LatexFlavorKindExt,
+        W: Write,
+        Fe: LatexFeatures,
+        T: Display,
+    {
+        if is_negative {
+            dest.write_char('-')?;
+        }
+        if let Some(whole) = whole {
+            write!(dest, "{}", whole)?;
+        }
+        let command = match self.fraction_style {
+            FractionStyle::Slash => {
+                return write!(dest, "{}/{}", numer, denom).map_err(Error::from)
+            }
+            FractionStyle::Frac | FractionStyle::Mixed => r"\frac",
+            FractionStyle::Dfrac => r"\dfrac",
+            FractionStyle::Tfrac => r"\tfrac",
+        };
+        if let LatexFlavorKind::PlainTex = Fl::KIND {
+            return match self.fraction_style {
+                FractionStyle::Frac | FractionStyle::Mixed => {
+                    write!(dest, r"{{{}\over {}}}", numer, denom).map_err(Error::from)
+                }
+                _ => Err(Error::UnsupportedByFlavor { construct: command }),
+            };
+        }
+        if let FractionStyle::Dfrac | FractionStyle::Tfrac = self.fraction_style {
+            features.require_package(Package::Amsmath);
+        }
+        write!(dest, "{}{{{}}}{{{}}}", command, numer, denom).map_err(Error::from)
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Floating-point numbers that can be written in a [`NumberFormat`].
+pub trait Float: ComplexPart + Copy + Display + LowerExp {
+    fn is_nan(self) -> bool;
+
+    fn is_infinite(self) -> bool;
+
+    fn is_sign_negative(self) -> bool;
+}
+
+macro_rules! impl_float {
+    ($($t:ident),+) => {
+        $(
+            impl Float for $t {
+                #[inline(always)]
+                fn is_nan(self) -> bool {
+                    $t::is_nan(self)
+                }
+
+                #[inline(always)]
+                fn is_infinite(self) -> bool {
+                    $t::is_infinite(self)
+                }
+
+                #[inline(always)]
+                fn is_sign_negative(self) -> bool {
+                    $t::is_sign_negative(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_float!(f32, f64);
+
+/// Implementors can be the real and imaginary parts of [`Complex`](nalgebra::Complex) numbers.
+pub trait ComplexPart: Coefficient {
+    /// Converts the number to [`f64`] for computing the polar form
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_complex_part {
+    ($($t:ident),+) => {
+        $(
+            impl ComplexPart for $t {
+                #[inline(always)]
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )+
+    };
+}
+
+impl_complex_part!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// Returns the numerator and the denominator of the fraction nearest to `|x|` among those
+// with the denominator not exceeding `max_denominator`, using continued fractions.
+fn nearest_fraction(x: f64, max_denominator: u64) -> Option<(u64, u64)> {
+    let x = if x < 0.0 { -x } else { x };
+    if x >= u64::MAX as f64 {
+        return None;
+    }
+    let max_denominator = max_denominator.max(1);
+    // The previous and the current convergents
+    let (mut p0, mut q0, mut p1, mut q1) = (0u64, 1u64, 1u64, 0u64);
+    let mut r = x;
+    // The convergents of an f64 converge long before 64 terms
+    for _ in 0..64 {
+        if r >= u64::MAX as f64 {
+            break;
+        }
+        let a = r as u64;
+        let (p2, q2) = match (
+            a.checked_mul(p1).and_then(|n| n.checked_add(p0)),
+            a.checked_mul(q1).and_then(|n| n.checked_add(q0)),
+        ) {
+            (Some(p2), Some(q2)) if q2 <= max_denominator => (p2, q2),
+            _ => {
+                // The best semiconvergent may be nearer than the last convergent
+                let k = (max_denominator - q0) / q1.max(1);
+                let (ps, qs) = (k * p1 + p0, k * q1 + q0);
+                let distance = |p: u64, q: u64| {
+                    let d = p as f64 / q as f64 - x;
+                    if d < 0.0 {
+                        -d
+                    } else {
+                        d
+                    }
+                };
+                if q1 == 0 || distance(ps, qs) < distance(p1, q1) {
+                    return Some((ps, qs));
+                }
+                break;
+            }
+        };
+        (p0, q0, p1, q1) = (p1, q1, p2, q2);
+        let fract = r - a as f64;
+        if fract == 0.0 || p1 as f64 / q1 as f64 == x {
+            break;
+        }
+        r = 1.0 / fract;
+    }
+    Some((p1, q1))
+}
+
+// A finite number rounded to the significant digits, split into the sign, the digits and
+// the decimal exponent of the first digit.
+struct Scientific {
+    is_negative: bool,
+    digits: FixedCapacityBuffer<MAX_DIGITS>,
+    exp: i32,
+}
+
+impl Scientific {
+    fn new<T: Float>(x: T, digits: usize) -> Result<Self, Error> {
+        let digits = digits.clamp(1, MAX_DIGITS);
+        // E.g. `-1.23e-20`, which is at most 8 bytes longer than the digits
+        let mut buf = FixedCapacityBuffer::<{ MAX_DIGITS + 8 }>::new();
+        write!(buf, "{:.*e}", digits - 1, x)?;
+        let (mantissa, exp) = buf
+            .as_str()
+            .split_once('e')
+            .ok_or(Error::Fmt(core::fmt::Error))?;
+        let mut sci = Scientific {
+            is_negative: mantissa.starts_with('-'),
+            digits: FixedCapacityBuffer::new(),
+            exp: exp.parse().map_err(|_| Error::Fmt(core::fmt::Error))?,
+        };
+        for d in mantissa.chars().filter(char::is_ascii_digit) {
+            sci.digits.write_char(d)?;
+        }
+        Ok(sci)
+    }
+
+    fn digits(&self) -> &str {
+        self.digits.as_str()
+    }
+
+    fn write_sign<W: Write>(&self, dest: &mut W) -> core::fmt::Result {
+        if self.is_negative {
+            dest.write_char('-')?;
+        }
+        Ok(())
+    }
+
+    // Writes the digits with `shift + 1` digits before the decimal point and the exponent
+    // decreased by `shift`, padding the digits with zeros if necessary.
+    fn write<W: Write>(&self, dest: &mut W, shift: usize, siunitx: bool) -> core::fmt::Result {
+        self.write_sign(dest)?;
+        let digits = self.digits();
+        let int_len = shift + 1;
+        if digits.len() > int_len {
+            dest.write_str(&digits[..int_len])?;
+            dest.write_char('.')?;
+            dest.write_str(&digits[int_len..])?;
+        } else {
+            dest.write_str(digits)?;
+            for _ in digits.len()..int_len {
+                dest.write_char('0')?;
+            }
+        }
+        let exp = self.exp - shift as i32;
+        if siunitx {
+            write!(dest, "e{}", exp)
+        } else if exp != 0 {
+            write!(dest, r" \times 10^{{{}}}", exp)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Writes the digits without the exponent, which must be less than the number of digits.
+    fn write_fixed<W: Write>(&self, dest: &mut W) -> core::fmt::Result {
+        self.write_sign(dest)?;
+        let digits = self.digits();
+        if self.exp >= 0 {
+            let int_len = self.exp as usize + 1;
+            dest.write_str(&digits[..int_len])?;
+            if digits.len() > int_len {
+                dest.write_char('.')?;
+                dest.write_str(&digits[int_len..])?;
+            }
+        } else {
+            dest.write_str("0.")?;
+            for _ in 1..-self.exp {
+                dest.write_char('0')?;
+            }
+            dest.write_str(digits)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Fl, Fe, IM, OM, I, F, P> LatexFormatter<Fl, Fe, Fe, IM, OM, I> for NumberFormatted<F, P>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    IM: LatexMode,
+    OM: LatexMode,
+    F: LatexFormatter<Fl, WithNumberFormat<Fe>, WithNumberFormat<Fe>, IM, OM, I>,
+    P: NumberFormatPolicy,
+{
+    fn fmt<IW, OW>(dest: IW, input: &I) -> Result<OW, Error>
+    where
+        IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = IM, NestedWriter = OW::NestedWriter>,
+        OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = OM>,
+    {
+        let (nested_writer, features) = dest.into_raw_parts();
+        let features = WithNumberFormat::new(features, P::FORMAT);
+        // Changing the number format cannot make the written LaTeX invalid
+        let dest: IW::WithFeatures<WithNumberFormat<Fe>> =
+            unsafe { LatexWriter::from_raw_parts(nested_writer, features) };
+        let dest: <IW::WithFeatures<WithNumberFormat<Fe>> as LatexWriter>::WithMode<OM> =
+            F::fmt(dest, input)?;
+        let (nested_writer, features) = dest.into_raw_parts();
+        Ok(unsafe { OW::from_raw_parts(nested_writer, features.into_inner()) })
+    }
+}
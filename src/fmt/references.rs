@@ -0,0 +1,208 @@
+//! Module with the registry of the labels and the references to them written in a document.
+//!
+//! A reference to a label that is never written is rendered as `??` by [LaTeX] and a label
+//! that is written twice makes the references to it ambiguous. Writers with the
+//! [`WithReferenceRegistry`] features record the keys of the labels written with
+//! [`WriteLabel`](crate::latex_writer::WriteLabel) and of the references written with
+//! [`WriteAsLatex`](crate::fmt::WriteAsLatex) so that such problems can be reported with
+//! [`ReferenceRegistry::report`] once the document is written.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     fmt::{
+//!         labels::{Counters, CountersChange, LabelGenerator},
+//!         WriteAsLatex,
+//!     },
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::DisplayMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! type W = Writer<AmsLatex, NoFeatures, DisplayMathMode, String>;
+//!
+//! let mut w = W::default().track_references();
+//! let mut counters = Counters::new();
+//! let first = unsafe { counters.write_next_label(&mut w, CountersChange::IncrementEquation) }
+//!     .unwrap();
+//! unsafe { counters.write_next_label(&mut w, CountersChange::IncrementEquation) }.unwrap();
+//!
+//! // The label is written to another document
+//! let mut appendix_counters = Counters::builder().with_prefix("appendix:").build();
+//! let appendix_label = unsafe {
+//!     appendix_counters.write_next_label(&mut W::default(), CountersChange::IncrementEquation)
+//! }
+//! .unwrap();
+//!
+//! let w: Writer<_, _, DisplayMathMode, _> = first.write_as_latex(w).unwrap();
+//! let w: Writer<_, _, DisplayMathMode, _> = appendix_label.write_as_latex(w).unwrap();
+//!
+//! let report = w.reference_registry().report();
+//! assert!(!report.is_ok());
+//! assert_eq!(report.dangling_references(), ["appendix:1"]);
+//! assert!(report.duplicate_labels().is_empty());
+//! assert_eq!(report.unreferenced_labels(), ["2"]);
+//! assert_eq!(
+//!     report.to_string(),
+//!     "dangling references: appendix:1; unreferenced labels: 2"
+//! );
+//! ```
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+//! [`WithReferenceRegistry`]: crate::latex_features::WithReferenceRegistry
+
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+use super::labels::Label;
+
+/// The keys of the labels and the references written so far, in the order they were written.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ReferenceRegistry {
+    labels: Vec<String>,
+    references: Vec<String>,
+}
+
+impl ReferenceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the key of the label, e.g. after writing `\label{...}`
+    pub fn record_label<L>(&mut self, label: &L)
+    where
+        L: Label + ?Sized,
+    {
+        if let Some(key) = key_of(label) {
+            self.labels.push(key);
+        }
+    }
+
+    /// Records the key of the label the reference refers to, e.g. after writing `\eqref{...}`
+    pub fn record_reference<L>(&mut self, label: &L)
+    where
+        L: Label + ?Sized,
+    {
+        if let Some(key) = key_of(label) {
+            self.references.push(key);
+        }
+    }
+
+    /// The keys of the recorded labels, including the duplicates
+    pub fn labels(&self) -> impl Iterator<Item = &str> + '_ {
+        self.labels.iter().map(String::as_str)
+    }
+
+    /// The keys of the labels the recorded references refer to, including the duplicates
+    pub fn references(&self) -> impl Iterator<Item = &str> + '_ {
+        self.references.iter().map(String::as_str)
+    }
+
+    /// Checks the recorded labels and references. The keys in the report are listed once,
+    /// in the order they were first recorded.
+    pub fn report(&self) -> ReferenceReport {
+        let labels: BTreeSet<&str> = self.labels().collect();
+        let references: BTreeSet<&str> = self.references().collect();
+
+        let mut seen = BTreeSet::new();
+        let dangling_references = self
+            .references()
+            .filter(|key| !labels.contains(key) && seen.insert(*key))
+            .map(String::from)
+            .collect();
+
+        let (mut seen, mut reported) = (BTreeSet::new(), BTreeSet::new());
+        let duplicate_labels = self
+            .labels()
+            .filter(|key| !seen.insert(*key) && reported.insert(*key))
+            .map(String::from)
+            .collect();
+
+        let mut seen = BTreeSet::new();
+        let unreferenced_labels = self
+            .labels()
+            .filter(|key| !references.contains(key) && seen.insert(*key))
+            .map(String::from)
+            .collect();
+
+        ReferenceReport {
+            dangling_references,
+            duplicate_labels,
+            unreferenced_labels,
+        }
+    }
+}
+
+fn key_of<L>(label: &L) -> Option<String>
+where
+    L: Label + ?Sized,
+{
+    let mut key = String::new();
+    label.write_key(&mut key).ok().map(|_| key)
+}
+
+/// The problems with the labels and the references found by [`ReferenceRegistry::report`].
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ReferenceReport {
+    dangling_references: Vec<String>,
+    duplicate_labels: Vec<String>,
+    unreferenced_labels: Vec<String>,
+}
+
+impl ReferenceReport {
+    /// The keys of the references to labels that have not been written,
+    /// which [LaTeX] renders as `??`
+    ///
+    /// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+    pub fn dangling_references(&self) -> &[String] {
+        &self.dangling_references
+    }
+
+    /// The keys of the labels that have been written more than once
+    pub fn duplicate_labels(&self) -> &[String] {
+        &self.duplicate_labels
+    }
+
+    /// The keys of the labels that have not been referenced
+    pub fn unreferenced_labels(&self) -> &[String] {
+        &self.unreferenced_labels
+    }
+
+    /// Checks whether the references would be rendered correctly, i.e. whether there are
+    /// neither dangling references nor duplicate labels. Unreferenced labels are allowed.
+    pub fn is_ok(&self) -> bool {
+        self.dangling_references.is_empty() && self.duplicate_labels.is_empty()
+    }
+
+    /// Checks whether no problems have been found, including unreferenced labels
+    pub fn is_clean(&self) -> bool {
+        self.is_ok() && self.unreferenced_labels.is_empty()
+    }
+}
+
+impl Display for ReferenceReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let sections = [
+            ("dangling references", &self.dangling_references),
+            ("duplicate labels", &self.duplicate_labels),
+            ("unreferenced labels", &self.unreferenced_labels),
+        ];
+        let mut is_first = true;
+        for (name, keys) in sections {
+            if keys.is_empty() {
+                continue;
+            }
+            if !is_first {
+                f.write_str("; ")?;
+            }
+            is_first = false;
+            write!(f, "{}: {}", name, keys.join(", "))?;
+        }
+        if is_first {
+            f.write_str("no problems with references")?;
+        }
+        Ok(())
+    }
+}
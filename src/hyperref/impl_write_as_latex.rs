@@ -1,36 +1,64 @@
 use core::fmt::Write;
 
-use crate::{fmt::WriteAsLatex, latex_modes::MathLatexMode, latex_writer::LatexWriter, latex_features::LatexFeatures};
+use crate::{
+    fmt::WriteAsLatex,
+    latex_features::{LatexFeatures, Package},
+    latex_flavors::AmsLatex,
+    latex_modes::{InnerParagraphMode, LRMode, MathLatexMode, OuterParagraphMode},
+    latex_writer::LatexWriter,
+};
 
-#[allow(deprecated)]
-use super::{SupportedFlavor, Hyperlink};
+use super::{Hyperlink, Hypertarget, SupportedFlavor};
 
-#[allow(deprecated)]
-impl<'a,Fl,Fe,M,NW,IW,OW> WriteAsLatex<Fl,Fe,Fe,M,M,NW,IW,OW> for Hyperlink<'a>
-where
-    Fl: SupportedFlavor,
-    Fe: LatexFeatures,
-    M: MathLatexMode,
-    NW: Write,
-    IW: LatexWriter<
-        Flavor = Fl,
-        Features = Fe,
-        Mode = M,
-        NestedWriter = NW,
-    >,
-    OW: LatexWriter<
-        Flavor = Fl,
-        Features = Fe,
-        Mode = M,
-        NestedWriter = NW,
-    >,
-{
-    // TODO: change implementation from the fallback to the actual implementation
-    fn write_as_latex(&self, dest: IW) -> Result<OW, core::fmt::Error> {
-        let (mut nested_writer, features) = dest.into_raw_parts();
-        nested_writer.write_str(r"\textit{")?;
-        nested_writer.write_str(&self.reference)?;
-        nested_writer.write_str(r"}")?;
-        Ok(unsafe { OW::from_raw_parts(nested_writer, features) })
-    }
-}
\ No newline at end of file
+macro_rules! impl_for_math_modes {
+    ($t:ident, $write:ident) => {
+        impl<'a, Fl, Fe, M, NW, IW, OW> WriteAsLatex<Fl, Fe, Fe, M, M, NW, IW, OW> for $t<'a>
+        where
+            Fl: SupportedFlavor,
+            Fe: LatexFeatures,
+            M: MathLatexMode,
+            NW: Write,
+            IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NW>,
+            OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NW>,
+        {
+            fn write_as_latex(&self, dest: IW) -> Result<OW, crate::error::Error> {
+                let (mut nested_writer, mut features) = dest.into_raw_parts();
+                if let Some(package) = Fl::PACKAGE {
+                    features.require_package(package);
+                }
+                Fl::$write(&mut nested_writer, self)?;
+                Ok(unsafe { OW::from_raw_parts(nested_writer, features) })
+            }
+        }
+    };
+}
+
+// Unlike MathJax, LaTeX also allows hyperlinks and hypertargets outside of math mode
+macro_rules! impl_for_horizontal_mode {
+    ($t:ident, $write:ident, $mode:ident) => {
+        impl<'a, Fe, NW, IW, OW> WriteAsLatex<AmsLatex, Fe, Fe, $mode, $mode, NW, IW, OW>
+            for $t<'a>
+        where
+            Fe: LatexFeatures,
+            NW: Write,
+            IW: LatexWriter<Flavor = AmsLatex, Features = Fe, Mode = $mode, NestedWriter = NW>,
+            OW: LatexWriter<Flavor = AmsLatex, Features = Fe, Mode = $mode, NestedWriter = NW>,
+        {
+            fn write_as_latex(&self, dest: IW) -> Result<OW, crate::error::Error> {
+                let (mut nested_writer, mut features) = dest.into_raw_parts();
+                features.require_package(Package::Hyperref);
+                AmsLatex::$write(&mut nested_writer, self)?;
+                Ok(unsafe { OW::from_raw_parts(nested_writer, features) })
+            }
+        }
+    };
+}
+
+impl_for_math_modes!(Hyperlink, write_hyperlink);
+impl_for_math_modes!(Hypertarget, write_hypertarget);
+impl_for_horizontal_mode!(Hyperlink, write_hyperlink, OuterParagraphMode);
+impl_for_horizontal_mode!(Hyperlink, write_hyperlink, InnerParagraphMode);
+impl_for_horizontal_mode!(Hyperlink, write_hyperlink, LRMode);
+impl_for_horizontal_mode!(Hypertarget, write_hypertarget, OuterParagraphMode);
+impl_for_horizontal_mode!(Hypertarget, write_hypertarget, InnerParagraphMode);
+impl_for_horizontal_mode!(Hypertarget, write_hypertarget, LRMode);
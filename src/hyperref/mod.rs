@@ -1,25 +1,169 @@
-use crate::latex_flavors::{LatexFlavor, AmsLatex, MathJax};
+//! Module with hyperlinks and their targets, e.g. for glossaries.
+//!
+//! With the [`AmsLatex`] flavor, [`Hyperlink`] and [`Hypertarget`] are written with the
+//! `\hyperlink` and `\hypertarget` commands from the [`hyperref`] package. [MathJax] has no
+//! such commands, so with the [`MathJax`] flavor the targets are written with `\cssId` and
+//! the hyperlinks are written with `\href` to the fragment of the target.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     fmt::WriteAsLatex,
+//!     hyperref::{Hyperlink, Hypertarget},
+//!     latex_features::NoFeatures,
+//!     latex_flavors::{AmsLatex, MathJax},
+//!     latex_modes::{InlineMathMode, InnerParagraphMode},
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! type AmsW = Writer<AmsLatex, NoFeatures, InnerParagraphMode, String>;
+//! type MathJaxW = Writer<MathJax, NoFeatures, InlineMathMode, String>;
+//!
+//! let target = Hypertarget::new("hadamard_product", "Hadamard product");
+//! let link = Hyperlink::new("matrix", "matrices");
+//!
+//! let w: AmsW = target.write_as_latex(AmsW::default()).unwrap();
+//! let w: AmsW = link.write_as_latex(w).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\hypertarget{hadamard_product}{\textbf{Hadamard product}}\hyperlink{matrix}{\textit{matrices}}"
+//! );
+//!
+//! let w: MathJaxW = target.write_as_latex(MathJaxW::default()).unwrap();
+//! let w: MathJaxW = link.write_as_latex(w).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\cssId{hadamard_product}{\textbf{Hadamard product}}\href{#matrix}{\textit{matrices}}"
+//! );
+//! ```
+//!
+//! Only the [`AmsLatex`] flavor requires the [`hyperref`] package. The references are escaped
+//! like by [`WriteText`], whereas the referents, which become anchors and HTML ids, may contain
+//! only ASCII letters, digits, `-`, `_`, `:` and `.`:
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     fmt::WriteAsLatex,
+//!     hyperref::Hyperlink,
+//!     latex_features::{NoFeatures, Package, RequiredPackages},
+//!     latex_flavors::{AmsLatex, MathJax},
+//!     latex_modes::InlineMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! type AmsW = Writer<AmsLatex, RequiredPackages, InlineMathMode, String>;
+//! type MathJaxW = Writer<MathJax, RequiredPackages, InlineMathMode, String>;
+//!
+//! let link = Hyperlink::new("cost", "50% off");
+//!
+//! let w = Writer::<AmsLatex, NoFeatures, InlineMathMode, String>::default().track_packages();
+//! let w: AmsW = link.write_as_latex(w).unwrap();
+//! assert!(w.required_packages().contains(Package::Hyperref));
+//! let (s, _required_packages) = w.into_raw_parts();
+//! assert_eq!(s, r"\hyperlink{cost}{\textit{50\% off}}");
+//!
+//! let w = Writer::<MathJax, NoFeatures, InlineMathMode, String>::default().track_packages();
+//! let w: MathJaxW = link.write_as_latex(w).unwrap();
+//! assert!(w.required_packages().is_empty());
+//! let (s, _required_packages) = w.into_raw_parts();
+//! assert_eq!(s, r"\href{#cost}{\textit{50}\%\textit{ off}}");
+//!
+//! let w = Writer::<AmsLatex, NoFeatures, InlineMathMode, String>::default();
+//! let res: Result<Writer<_, _, InlineMathMode, _>, _> =
+//!     Hyperlink::new("50%", "cost").write_as_latex(w);
+//! assert!(res.is_err());
+//! ```
+//!
+//! A link whose target is not written anywhere in the document is broken. To avoid that,
+//! hyperlinks and hypertargets can be written through a [`TargetRegistry`], which records the
+//! written hypertargets and resolves the hyperlinks against them. The unresolved hyperlinks are
+//! written according to the [`UnresolvedLinkFallback`] of the registry.
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     hyperref::{Hyperlink, Hypertarget, TargetRegistry, UnresolvedLinkFallback},
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::InnerParagraphMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! type W = Writer<AmsLatex, NoFeatures, InnerParagraphMode, String>;
+//!
+//! let mut registry = TargetRegistry::new(UnresolvedLinkFallback::Italic);
+//! let w = registry
+//!     .write_hypertarget(&Hypertarget::new("matrix", "Matrix"), W::default())
+//!     .unwrap();
+//! let w = registry
+//!     .write_hyperlink(&Hyperlink::new("matrix", "matrices"), w)
+//!     .unwrap();
+//! let w = registry
+//!     .write_hyperlink(&Hyperlink::new("tensor", "tensors"), w)
+//!     .unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\hypertarget{matrix}{\textbf{Matrix}}\hyperlink{matrix}{\textit{matrices}}\textit{tensors}"
+//! );
+//!
+//! let registry = TargetRegistry::new(UnresolvedLinkFallback::Error);
+//! assert!(registry
+//!     .write_hyperlink(&Hyperlink::new("tensor", "tensors"), W::default())
+//!     .is_err());
+//! ```
+//!
+//! [`hyperref`]: https://ctan.org/pkg/hyperref
+//! [`WriteText`]: crate::text::WriteText
+//! [MathJax]: https://www.mathjax.org/
+
+use core::fmt::Write;
+
+use crate::{
+    error::Error,
+    latex_features::Package,
+    latex_flavors::{AmsLatex, LatexFlavor, LatexFlavorKindExt, MathJax},
+    text::write_escaped_math_text_in,
+};
 
 mod impl_write_as_latex;
+mod registry;
+
+pub use registry::{TargetRegistry, UnresolvedLinkFallback};
+
+pub trait SupportedFlavor: LatexFlavor + LatexFlavorKindExt {
+    /// The package that provides the commands, if any
+    const PACKAGE: Option<Package>;
+
+    fn is_referencable(registry: &TargetRegistry, link: &Hyperlink) -> bool;
+
+    fn write_hyperlink<W>(dest: &mut W, link: &Hyperlink) -> Result<(), Error>
+    where
+        W: Write;
 
-pub trait SupportedFlavor: LatexFlavor {
-    #[allow(deprecated)]
-    fn is_referencable<'a,'b>(label: &'a Hyperlink<'b>) -> bool;
+    fn write_hypertarget<W>(dest: &mut W, target: &Hypertarget) -> Result<(), Error>
+    where
+        W: Write;
 }
 
-#[cfg_attr(
-    not(feature = "silence_deprecation"),
-    deprecated(since = "0.1.0", note = r"The implementation is a fallback to \textit{text}")
-)]
+/// A link to a [`Hypertarget`] with the same referent.
 pub struct Hyperlink<'a> {
     /// e.g. `hadamard_product`
-    #[allow(dead_code)]
     referent: &'a str,
     /// e.g. `Hadamard product`
     reference: &'a str,
 }
 
-#[allow(deprecated)]
+/// A target of [`Hyperlink`]s with the same referent, e.g. the definition of a term.
+pub struct Hypertarget<'a> {
+    /// e.g. `hadamard_product`
+    referent: &'a str,
+    /// e.g. `Hadamard product`
+    reference: &'a str,
+}
+
 impl<'a> Hyperlink<'a> {
     pub fn new(referent: &'a str, reference: &'a str) -> Self {
         Self {
@@ -27,18 +171,119 @@ impl<'a> Hyperlink<'a> {
             reference,
         }
     }
+
+    pub fn referent(&self) -> &'a str {
+        self.referent
+    }
+
+    pub fn reference(&self) -> &'a str {
+        self.reference
+    }
+}
+
+impl<'a> Hypertarget<'a> {
+    pub fn new(referent: &'a str, reference: &'a str) -> Self {
+        Self {
+            referent,
+            reference,
+        }
+    }
+
+    pub fn referent(&self) -> &'a str {
+        self.referent
+    }
+
+    pub fn reference(&self) -> &'a str {
+        self.reference
+    }
+}
+
+// Referents become anchors and HTML ids, in which the special characters cannot be escaped
+fn validate_referent(referent: &str) -> Result<(), Error> {
+    let is_valid = !referent.is_empty()
+        && referent
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'));
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput {
+            reason: "referents may contain only ASCII letters, digits, `-`, `_`, `:` and `.`",
+        })
+    }
+}
+
+// Writes `\command{referent}{\text_command{reference}}` with the referent prefixed with `prefix`
+fn write_link<Fl, W>(
+    dest: &mut W,
+    command: &str,
+    prefix: &str,
+    referent: &str,
+    text_command: &str,
+    reference: &str,
+) -> Result<(), Error>
+where
+    Fl: SupportedFlavor,
+    W: Write,
+{
+    validate_referent(referent)?;
+    dest.write_str(command)?;
+    dest.write_char('{')?;
+    dest.write_str(prefix)?;
+    dest.write_str(referent)?;
+    dest.write_str("}{")?;
+    write_escaped_math_text_in::<Fl, _>(dest, text_command, reference)?;
+    dest.write_char('}')?;
+    Ok(())
 }
 
 impl SupportedFlavor for AmsLatex {
-    #[allow(deprecated)]
-    fn is_referencable<'a,'b>(_label: &'a Hyperlink<'b>) -> bool {
-        todo!()
+    const PACKAGE: Option<Package> = Some(Package::Hyperref);
+
+    fn is_referencable(registry: &TargetRegistry, link: &Hyperlink) -> bool {
+        registry.contains(link.referent)
+    }
+
+    fn write_hyperlink<W>(dest: &mut W, link: &Hyperlink) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        write_link::<Self, _>(dest, r"\hyperlink", "", link.referent, r"\textit", link.reference)
+    }
+
+    fn write_hypertarget<W>(dest: &mut W, target: &Hypertarget) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        write_link::<Self, _>(
+            dest,
+            r"\hypertarget",
+            "",
+            target.referent,
+            r"\textbf",
+            target.reference,
+        )
     }
 }
 
 impl SupportedFlavor for MathJax {
-    #[allow(deprecated)]
-    fn is_referencable<'a,'b>(_label: &'a Hyperlink<'b>) -> bool {
-        todo!()
+    const PACKAGE: Option<Package> = None;
+
+    fn is_referencable(registry: &TargetRegistry, link: &Hyperlink) -> bool {
+        registry.contains(link.referent)
     }
-}
\ No newline at end of file
+
+    fn write_hyperlink<W>(dest: &mut W, link: &Hyperlink) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        write_link::<Self, _>(dest, r"\href", "#", link.referent, r"\textit", link.reference)
+    }
+
+    fn write_hypertarget<W>(dest: &mut W, target: &Hypertarget) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        write_link::<Self, _>(dest, r"\cssId", "", target.referent, r"\textbf", target.reference)
+    }
+}
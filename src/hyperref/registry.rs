@@ -0,0 +1,99 @@
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::{Hyperlink, Hypertarget, SupportedFlavor};
+use crate::{
+    error::Error,
+    fmt::WriteAsLatex, latex_features::LatexFeatures, latex_modes::LatexMode,
+    latex_writer::LatexWriter, text::write_escaped_math_text_in,
+};
+
+/// The way a [`Hyperlink`] without a known [`Hypertarget`] is written.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnresolvedLinkFallback {
+    /// The reference is written in italics, e.g. `\textit{matrices}`
+    Italic,
+    /// The reference is written as plain text, e.g. `\textrm{matrices}`
+    Plain,
+    /// Writing the hyperlink fails with [`Error::InvalidInput`](crate::error::Error::InvalidInput)
+    Error,
+}
+
+/// The registry of [`Hypertarget`]s that have been written in the current document.
+pub struct TargetRegistry<'a> {
+    referents: Vec<&'a str>,
+    fallback: UnresolvedLinkFallback,
+}
+
+impl<'a> TargetRegistry<'a> {
+    pub fn new(fallback: UnresolvedLinkFallback) -> Self {
+        Self {
+            referents: Vec::new(),
+            fallback,
+        }
+    }
+
+    pub fn fallback(&self) -> UnresolvedLinkFallback {
+        self.fallback
+    }
+
+    /// Checks whether the hypertarget with the given referent has been registered
+    pub fn contains(&self, referent: &str) -> bool {
+        self.referents.contains(&referent)
+    }
+
+    /// Registers the referent of a hypertarget that is written elsewhere, e.g. further in
+    /// the document, so that the hyperlinks to it can be resolved before it is written.
+    pub fn declare(&mut self, referent: &'a str) {
+        if !self.contains(referent) {
+            self.referents.push(referent);
+        }
+    }
+
+    /// Writes the hypertarget and registers its referent
+    pub fn write_hypertarget<Fl, Fe, M, NW, W>(
+        &mut self,
+        target: &Hypertarget<'a>,
+        dest: W,
+    ) -> Result<W, Error>
+    where
+        Fl: SupportedFlavor,
+        Fe: LatexFeatures,
+        M: LatexMode,
+        NW: Write,
+        W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NW>,
+        for<'b> Hypertarget<'b>: WriteAsLatex<Fl, Fe, Fe, M, M, NW, W, W>,
+    {
+        let dest = target.write_as_latex(dest)?;
+        self.declare(target.referent);
+        Ok(dest)
+    }
+
+    /// Writes the hyperlink if its hypertarget has been registered and writes
+    /// the fallback otherwise
+    pub fn write_hyperlink<Fl, Fe, M, NW, W>(&self, link: &Hyperlink, dest: W) -> Result<W, Error>
+    where
+        Fl: SupportedFlavor,
+        Fe: LatexFeatures,
+        M: LatexMode,
+        NW: Write,
+        W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NW>,
+        for<'b> Hyperlink<'b>: WriteAsLatex<Fl, Fe, Fe, M, M, NW, W, W>,
+    {
+        if Fl::is_referencable(self, link) {
+            return link.write_as_latex(dest);
+        }
+        let command = match self.fallback {
+            UnresolvedLinkFallback::Italic => r"\textit",
+            UnresolvedLinkFallback::Plain => r"\textrm",
+            UnresolvedLinkFallback::Error => {
+                return Err(Error::InvalidInput {
+                    reason: "the hyperlink refers to an undeclared hypertarget",
+                })
+            }
+        };
+        let (mut nested_writer, features) = dest.into_raw_parts();
+        write_escaped_math_text_in::<Fl, _>(&mut nested_writer, command, link.reference)?;
+        Ok(unsafe { W::from_raw_parts(nested_writer, features) })
+    }
+}
@@ -0,0 +1,132 @@
+//! Module with an adapter that lets [`Writer`] write to a [`std::io::Write`] implementor,
+//! e.g. a [`File`], a [`BufWriter`] or [`Stdout`], without an intermediate [`String`].
+//!
+//! [`core::fmt::Write`] can fail only with the opaque [`core::fmt::Error`], so
+//! [`IoWriteAdapter`] stores the [`io::Error`] that caused the failure. It can be
+//! retrieved after the formatter has returned [`Error::Fmt`].
+//!
+//! # Example
+//!
+//! ```
+//! use std::io;
+//!
+//! use nalgebra::matrix;
+//! use nalgebra_latex::{
+//!     error::Error,
+//!     fmt::LatexFormatter,
+//!     io::IoWriteAdapter,
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_format,
+//!     latex_modes::{InlineMathMode, InnerParagraphMode},
+//!     latex_writer::{LatexWriter, Writer},
+//!     matrix::fmt::PlainMatrixFormatter,
+//! };
+//!
+//! fn write_report<W: core::fmt::Write>(
+//!     w: Writer<AmsLatex, NoFeatures, InnerParagraphMode, W>,
+//! ) -> Result<Writer<AmsLatex, NoFeatures, InnerParagraphMode, W>, Error> {
+//!     let m = matrix![1, 2; 3, 4];
+//!     latex_format!(
+//!         #[on_format_error(?)]
+//!         w += "Let " ; "$" ;
+//!         |w: Writer<AmsLatex, NoFeatures, InlineMathMode, W>| {
+//!             let w: Writer<_, _, InlineMathMode, _> = PlainMatrixFormatter::fmt(w, &m)?;
+//!             Ok(w)
+//!         };
+//!         "$" ;
+//!     );
+//!     Ok(w)
+//! }
+//!
+//! let w = write_report(LatexWriter::new(IoWriteAdapter::new(Vec::new()))).unwrap();
+//! let (adapter, _no_features) = w.into_raw_parts();
+//! let bytes: Vec<u8> = adapter.finish().unwrap();
+//! assert_eq!(
+//!     String::from_utf8(bytes).unwrap(),
+//!     r"Let $\begin{matrix}1&2\\3&4\end{matrix}$"
+//! );
+//!
+//! // Writing past the end of the slice fails and the I/O error is preserved
+//! let mut buf = [0u8; 8];
+//! let mut adapter = IoWriteAdapter::new(&mut buf[..]);
+//! assert_eq!(
+//!     write_report(LatexWriter::new(&mut adapter)).map(|_| ()),
+//!     Err(Error::Fmt(core::fmt::Error))
+//! );
+//! assert_eq!(adapter.take_error().unwrap().kind(), io::ErrorKind::WriteZero);
+//! ```
+//!
+//! [`Writer`]: crate::latex_writer::Writer
+//! [`File`]: std::fs::File
+//! [`BufWriter`]: std::io::BufWriter
+//! [`Stdout`]: std::io::Stdout
+//! [`Error::Fmt`]: crate::error::Error::Fmt
+
+use std::io;
+
+/// Nested writer that forwards the written [LaTeX] to an [`io::Write`] implementor.
+///
+/// The first [`io::Error`] is stored and every subsequent write fails, so that a partially
+/// written stream is never silently continued.
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+#[derive(Debug)]
+pub struct IoWriteAdapter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W> IoWriteAdapter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the [`io::Error`] that made writing fail, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Takes the [`io::Error`] that made writing fail, if any, so that writing can be resumed.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    pub fn into_parts(self) -> (W, Option<io::Error>) {
+        (self.inner, self.error)
+    }
+
+    /// Returns the wrapped writer or the [`io::Error`] that made writing fail.
+    pub fn finish(self) -> io::Result<W> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+impl<W: io::Write> IoWriteAdapter<W> {
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: io::Write> core::fmt::Write for IoWriteAdapter<W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        if self.error.is_some() {
+            return Err(core::fmt::Error);
+        }
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            core::fmt::Error
+        })
+    }
+}
@@ -1,5 +1,286 @@
-pub trait LatexFeatures {}
+//! Module with types representing features of [LaTeX] writers.
+//!
+//! The main feature is the tracking of the [LaTeX] packages required by the
+//! written [LaTeX]. Writers with [`NoFeatures`] ignore the requirements, whereas writers with
+//! [`RequiredPackages`] record them so that the exact list of `\usepackage` commands can be
+//! written in the preamble of the document.
+//!
+//! Additionally, [`WithNumberFormat`] attaches a [`NumberFormat`] to the writer, see
+//! [`fmt::number`](crate::fmt::number), and `WithReferenceRegistry` records the written
+//! labels and references to them, see [`fmt::references`](crate::fmt::references).
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra::matrix;
+//! use nalgebra_latex::{
+//!     fmt::LatexFormatter,
+//!     latex_features::{NoFeatures, Package, RequiredPackages},
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::DisplayMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//!     matrix::fmt::ParenthesizedMatrixFormatter,
+//! };
+//!
+//! type W = Writer<AmsLatex, RequiredPackages, DisplayMathMode, String>;
+//!
+//! let w: W = Writer::<AmsLatex, NoFeatures, DisplayMathMode, String>::default().track_packages();
+//! let w: W = ParenthesizedMatrixFormatter::fmt(w, &matrix![1, 2; 3, 4]).unwrap();
+//!
+//! let packages = w.required_packages();
+//! assert!(packages.contains(Package::Amsmath));
+//! assert!(!packages.contains(Package::Hyperref));
+//!
+//! let mut preamble = String::new();
+//! packages.write_usepackages(&mut preamble).unwrap();
+//! assert_eq!(preamble, "\\usepackage{amsmath}\n");
+//! ```
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+
+#[cfg(feature = "alloc")]
+use crate::fmt::references::ReferenceRegistry;
+use crate::fmt::{labels::Label, number::NumberFormat};
+
+pub trait LatexFeatures {
+    /// Records that the written [LaTeX] requires the package. By default, the requirement is ignored.
+    ///
+    /// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+    #[inline(always)]
+    fn require_package(&mut self, _package: Package) {}
+
+    /// The format of floating-point numbers. By default, it is [`NumberFormat::DEFAULT`].
+    #[inline(always)]
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat::DEFAULT
+    }
+
+    /// Records that the label has been written with `\label`. By default, the label is ignored.
+    #[inline(always)]
+    fn record_label<L>(&mut self, _label: &L)
+    where
+        L: Label + ?Sized,
+    {
+    }
+
+    /// Records that a reference to the label has been written, e.g. with `\eqref`.
+    /// By default, the reference is ignored.
+    #[inline(always)]
+    fn record_reference<L>(&mut self, _label: &L)
+    where
+        L: Label + ?Sized,
+    {
+    }
+}
 
 pub struct NoFeatures;
 
 impl LatexFeatures for NoFeatures {}
+
+macro_rules! decl_packages {
+    ($($package:ident => $name:literal,)+) => {
+        /// A [LaTeX] package that can be required by the written [LaTeX].
+        ///
+        /// The packages are listed in the order they should be loaded in. In particular,
+        /// `hyperref` should be loaded after most other packages.
+        ///
+        /// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        pub enum Package {
+            $(
+                $package,
+            )+
+        }
+
+        impl Package {
+            pub const ALL: &'static [Package] = &[$(Package::$package,)+];
+
+            /// The name of the package, e.g. `amsmath`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Package::$package => $name,
+                    )+
+                }
+            }
+        }
+    };
+}
+
+decl_packages! {
+    Amsmath => "amsmath",
+    Amssymb => "amssymb",
+    Mathtools => "mathtools",
+    Systeme => "systeme",
+    Siunitx => "siunitx",
+    Hyperref => "hyperref",
+}
+
+/// The set of [LaTeX] packages required by everything written so far.
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct RequiredPackages {
+    bits: u32,
+}
+
+impl RequiredPackages {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, package: Package) {
+        self.bits |= 1 << package as u32;
+    }
+
+    pub fn contains(&self, package: Package) -> bool {
+        self.bits & (1 << package as u32) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Iterates over the required packages in the order they should be loaded in
+    pub fn iter(&self) -> impl Iterator<Item = Package> + '_ {
+        Package::ALL.iter().copied().filter(move |p| self.contains(*p))
+    }
+
+    /// Writes a `\usepackage` command on a separate line for every required package
+    pub fn write_usepackages<W>(&self, dest: &mut W) -> Result<(), core::fmt::Error>
+    where
+        W: core::fmt::Write,
+    {
+        for package in self.iter() {
+            dest.write_str(r"\usepackage{")?;
+            dest.write_str(package.name())?;
+            dest.write_str("}\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl LatexFeatures for RequiredPackages {
+    #[inline(always)]
+    fn require_package(&mut self, package: Package) {
+        self.insert(package);
+    }
+}
+
+impl From<NoFeatures> for RequiredPackages {
+    fn from(_: NoFeatures) -> Self {
+        Self::new()
+    }
+}
+
+/// The features `Fe` with the [`NumberFormat`] used for floating-point numbers.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WithNumberFormat<Fe> {
+    features: Fe,
+    number_format: NumberFormat,
+}
+
+impl<Fe> WithNumberFormat<Fe> {
+    pub fn new(features: Fe, number_format: NumberFormat) -> Self {
+        Self {
+            features,
+            number_format,
+        }
+    }
+
+    pub fn inner(&self) -> &Fe {
+        &self.features
+    }
+
+    pub fn into_inner(self) -> Fe {
+        self.features
+    }
+}
+
+impl<Fe: LatexFeatures> LatexFeatures for WithNumberFormat<Fe> {
+    #[inline(always)]
+    fn require_package(&mut self, package: Package) {
+        self.features.require_package(package);
+    }
+
+    #[inline(always)]
+    fn number_format(&self) -> NumberFormat {
+        self.number_format
+    }
+
+    #[inline(always)]
+    fn record_label<L>(&mut self, label: &L)
+    where
+        L: Label + ?Sized,
+    {
+        self.features.record_label(label);
+    }
+
+    #[inline(always)]
+    fn record_reference<L>(&mut self, label: &L)
+    where
+        L: Label + ?Sized,
+    {
+        self.features.record_reference(label);
+    }
+}
+
+/// The features `Fe` with the [`ReferenceRegistry`] of the written labels and references.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct WithReferenceRegistry<Fe> {
+    features: Fe,
+    registry: ReferenceRegistry,
+}
+
+#[cfg(feature = "alloc")]
+impl<Fe> WithReferenceRegistry<Fe> {
+    pub fn new(features: Fe) -> Self {
+        Self {
+            features,
+            registry: ReferenceRegistry::new(),
+        }
+    }
+
+    pub fn inner(&self) -> &Fe {
+        &self.features
+    }
+
+    pub fn registry(&self) -> &ReferenceRegistry {
+        &self.registry
+    }
+
+    pub fn into_parts(self) -> (Fe, ReferenceRegistry) {
+        (self.features, self.registry)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Fe: LatexFeatures> LatexFeatures for WithReferenceRegistry<Fe> {
+    #[inline(always)]
+    fn require_package(&mut self, package: Package) {
+        self.features.require_package(package);
+    }
+
+    #[inline(always)]
+    fn number_format(&self) -> NumberFormat {
+        self.features.number_format()
+    }
+
+    fn record_label<L>(&mut self, label: &L)
+    where
+        L: Label + ?Sized,
+    {
+        self.features.record_label(label);
+        self.registry.record_label(label);
+    }
+
+    fn record_reference<L>(&mut self, label: &L)
+    where
+        L: Label + ?Sized,
+    {
+        self.features.record_reference(label);
+        self.registry.record_reference(label);
+    }
+}
@@ -151,3 +151,84 @@ decl_latex_modes! {
         mode:InternalVerticalMode
     }
 }
+
+/// Implementors are the paragraph modes, i.e. the horizontal modes from which [LaTeX]
+/// can enter display math, e.g. with `\begin{equation}`.
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+pub trait ParagraphLatexMode: HorizontalLatexMode {}
+
+impl ParagraphLatexMode for OuterParagraphMode {}
+impl ParagraphLatexMode for InnerParagraphMode {}
+
+/// Implementors are modes from which [LaTeX] can enter the mode `M` with [`OPENING`] and
+/// return with [`CLOSING`].
+///
+/// # Safety
+///
+/// [`OPENING`] must make [LaTeX] enter the mode `M` and [`CLOSING`] must make it return
+/// to `Self`.
+///
+/// # Example
+///
+/// ```
+/// use nalgebra_latex::{
+///     latex_features::NoFeatures,
+///     latex_flavors::AmsLatex,
+///     latex_modes::{InlineMathMode, InternalVerticalMode, LRMode, VerticalMode},
+///     latex_writer::{LatexWriter, Writer},
+///     text::WriteText,
+/// };
+///
+/// let w = Writer::<AmsLatex, NoFeatures, VerticalMode, String>::default();
+/// let w = w
+///     .write_in_mode(|w| {
+///         w.write_in_mode::<InlineMathMode, _>(|w| {
+///             w.write_in_mode(|mut w: Writer<_, _, LRMode, _>| {
+///                 w.write_text("if ")?;
+///                 Ok(w)
+///             })
+///         })?
+///         .write_in_mode::<InternalVerticalMode, _>(Ok)
+///     })
+///     .unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(s, "$\\mbox{if }$\\vbox{}\n\n");
+/// ```
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+/// [`OPENING`]: ModeTransition::OPENING
+/// [`CLOSING`]: ModeTransition::CLOSING
+pub unsafe trait ModeTransition<M: LatexMode>: LatexMode {
+    const OPENING: &'static str;
+    const CLOSING: &'static str;
+}
+
+macro_rules! decl_mode_transitions {
+    ($($($from:ident)|+ => $to:ident: $opening:literal, $closing:literal;)+) => {
+        $(
+            $(
+                unsafe impl ModeTransition<$to> for $from {
+                    const OPENING: &'static str = $opening;
+                    const CLOSING: &'static str = $closing;
+                }
+            )+
+        )+
+    };
+}
+
+// http://latexref.xyz/Modes.html
+decl_mode_transitions! {
+    // Paragraphs in the main text are started by anything written in vertical mode
+    VerticalMode => OuterParagraphMode: "", "\n\n";
+    OuterParagraphMode => VerticalMode: "\n\n", "";
+    // Paragraphs in boxes
+    InternalVerticalMode => InnerParagraphMode: "", "\n\n";
+    InnerParagraphMode => InternalVerticalMode: "\n\n", "";
+    OuterParagraphMode | LRMode | InlineMathMode | DisplayMathMode
+        => InternalVerticalMode: r"\vbox{", "}";
+    OuterParagraphMode | InnerParagraphMode | LRMode => InlineMathMode: "$", "$";
+    OuterParagraphMode | InnerParagraphMode => DisplayMathMode: "$$", "$$";
+    OuterParagraphMode | InnerParagraphMode | InlineMathMode | DisplayMathMode
+        => LRMode: r"\mbox{", "}";
+}
@@ -1,33 +1,45 @@
 use core::{fmt::Error, marker::PhantomData};
 
 use crate::{
-    latex_features::{LatexFeatures, NoFeatures},
+    latex_features::{LatexFeatures, NoFeatures, Package, RequiredPackages, WithNumberFormat},
     latex_flavors::{LatexFlavorKindExt, MathJax, AmsLatex},
-    latex_modes::{DisplayMathMode, InlineMathMode, InnerParagraphMode, LatexMode}, fmt::labels::Label,
+    latex_modes::{
+        DisplayMathMode, InlineMathMode, InnerParagraphMode, LatexMode, ModeTransition,
+        OuterParagraphMode, VerticalMode,
+    },
+    fmt::{labels::Label, number::NumberFormat},
+    math_delimiters::MathDelimiters,
 };
+#[cfg(feature = "alloc")]
+use crate::{fmt::references::ReferenceRegistry, latex_features::WithReferenceRegistry};
 
+/// Writing of unchecked [LaTeX], the counterpart of [`core::fmt::Write`].
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
 pub trait UnsafeWrite {
+    /// # Safety
+    ///
+    /// `s` must be valid in the mode of the writer and must not change the mode,
+    /// e.g. by opening a group or an environment that it does not close.
     unsafe fn write_str(&mut self, s: &str) -> Result<(), Error>;
+    /// # Safety
+    ///
+    /// See [`UnsafeWrite::write_str`].
     unsafe fn write_char(&mut self, c: char) -> core::fmt::Result;
+    /// # Safety
+    ///
+    /// See [`UnsafeWrite::write_str`].
     unsafe fn write_fmt(&mut self, args: core::fmt::Arguments) -> core::fmt::Result;
 }
 
 pub trait WriteTwoDollarSignsTargetExt: LatexWriter {
-    type WriteTwoDollarSignsTarget: LatexWriter<
-        NestedWriter = Self::NestedWriter,
-        Flavor = Self::Flavor,
-        Features = Self::Features,
-    >;
+    /// The mode that is entered (or returned to) with `$$`
+    type TwoDollarSignsTargetMode: LatexMode;
 }
 
 pub trait WriteDollarSignTargetExt: LatexWriter {
-    type WriteDollarSignTarget: LatexWriter<
-        NestedWriter = Self::NestedWriter,
-        Flavor = Self::Flavor,
-        Features = Self::Features,
-    >
-        // This doesn't seem to be deduced by the compiler but keeping it doesn't hurt
-        + WriteDollarSignTargetExt<WriteDollarSignTarget = Self>;
+    /// The mode that is entered (or returned to) with `$`
+    type DollarSignTargetMode: LatexMode;
 }
 
 pub trait WriteLabel: Sized + LatexWriter<Mode = DisplayMathMode> {
@@ -42,44 +54,35 @@ pub trait LatexWriter: UnsafeWrite {
     type Features: LatexFeatures;
     type Mode: LatexMode;
 
-    // With GATs, the following types will be pointless in the trait.
-    #[deprecated(
-        since = "0.1.0",
-        note = "This type together with other deprecated types in the trait is a temporary solution which
-        soon will be swapped for one GAT."
-    )]
-    type InnerParagraphWriter: LatexWriter<
-        Flavor = Self::Flavor,
-        Features = Self::Features,
-        Mode = InnerParagraphMode,
-        NestedWriter = Self::NestedWriter,
-    >;
-    #[deprecated(
-        since = "0.1.0",
-        note = "This type together with other deprecated types in the trait is a temporary solution which
-        soon will be swapped for one GAT."
-    )]
-    type DisplayMathWriter: LatexWriter<
+    /// The writer that differs from `Self` only in the mode
+    type WithMode<M: LatexMode>: LatexWriter<
         Flavor = Self::Flavor,
         Features = Self::Features,
-        Mode = DisplayMathMode,
+        Mode = M,
         NestedWriter = Self::NestedWriter,
     >;
-    #[deprecated(
-        since = "0.1.0",
-        note = "This type together with other deprecated types in the trait is a temporary solution which
-        soon will be swapped for one GAT."
-    )]
-    type InlineMathWriter: LatexWriter<
+    /// The writer that differs from `Self` only in the features
+    type WithFeatures<Fe: LatexFeatures>: LatexWriter<
         Flavor = Self::Flavor,
-        Features = Self::Features,
-        Mode = InlineMathMode,
+        Features = Fe,
+        Mode = Self::Mode,
         NestedWriter = Self::NestedWriter,
     >;
 
     fn into_raw_parts(self) -> (Self::NestedWriter, Self::Features);
+    /// # Safety
+    ///
+    /// The [LaTeX] written to `w` so far must leave [LaTeX] in the mode of the writer and
+    /// `features` must describe it, e.g. list the packages it requires.
+    ///
+    /// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
     unsafe fn from_raw_parts(w: Self::NestedWriter, features: Self::Features) -> Self;
 
+    /// Converts the writer into another writer with the same flavor, features and mode.
+    ///
+    /// # Safety
+    ///
+    /// See [`LatexWriter::from_raw_parts`].
     #[inline(always)]
     unsafe fn rebuild<T>(self) -> T
     where
@@ -95,6 +98,101 @@ pub trait LatexWriter: UnsafeWrite {
         T::from_raw_parts(nested_writer, features)
     }
 
+    /// # Safety
+    ///
+    /// The [LaTeX] written so far must leave [LaTeX] in the mode `M`.
+    ///
+    /// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+    #[inline(always)]
+    unsafe fn switch_mode_unchecked<M: LatexMode>(self) -> Self::WithMode<M>
+    where
+        Self: Sized,
+    {
+        let (nested_writer, features) = self.into_raw_parts();
+        <Self::WithMode<M>>::from_raw_parts(nested_writer, features)
+    }
+
+    /// # Safety
+    ///
+    /// The features converted from the current ones must describe the [LaTeX] written so far.
+    ///
+    /// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+    #[inline(always)]
+    unsafe fn switch_features_unchecked<Fe>(self) -> Self::WithFeatures<Fe>
+    where
+        Self: Sized,
+        Fe: LatexFeatures + From<Self::Features>,
+    {
+        let (nested_writer, features) = self.into_raw_parts();
+        <Self::WithFeatures<Fe>>::from_raw_parts(nested_writer, features.into())
+    }
+
+    /// Enters the mode `M`, writes in it using the closure and returns to the current mode.
+    ///
+    /// The code that makes [LaTeX] enter and leave the mode is given by [`ModeTransition`].
+    ///
+    /// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+    fn write_in_mode<M, F>(mut self, f: F) -> Result<Self, crate::error::Error>
+    where
+        Self: Sized,
+        Self::Mode: ModeTransition<M>,
+        M: LatexMode,
+        F: FnOnce(Self::WithMode<M>) -> Result<Self::WithMode<M>, crate::error::Error>,
+    {
+        unsafe { self.write_str(<Self::Mode as ModeTransition<M>>::OPENING) }?;
+        let mut w = f(unsafe { self.switch_mode_unchecked() })?;
+        unsafe { w.write_str(<Self::Mode as ModeTransition<M>>::CLOSING) }?;
+        let (nested_writer, features) = w.into_raw_parts();
+        Ok(unsafe { Self::from_raw_parts(nested_writer, features) })
+    }
+
+    /// Enters math mode with the opening delimiter of `D`, e.g. `\(`.
+    fn open_math<D>(mut self) -> Result<Self::WithMode<D::Mode>, Error>
+    where
+        Self: Sized,
+        Self::Mode: ModeTransition<D::Mode>,
+        D: MathDelimiters<Self::Flavor>,
+    {
+        if let Some(package) = D::PACKAGE {
+            self.require_package(package);
+        }
+        unsafe { self.write_str(D::OPENING) }?;
+        Ok(unsafe { self.switch_mode_unchecked() })
+    }
+
+    /// Leaves math mode with the closing delimiter of `D`, e.g. `\)`, and returns to the mode `M`
+    /// math was opened from.
+    ///
+    /// Nothing checks that the math was opened from `M`, so prefer [`LatexWriter::write_math`],
+    /// which returns to the current mode by construction.
+    ///
+    /// # Safety
+    ///
+    /// The math block must have been opened with [`LatexWriter::open_math`] from the mode `M`.
+    unsafe fn close_math<D, M>(mut self) -> Result<Self::WithMode<M>, Error>
+    where
+        Self: Sized,
+        D: MathDelimiters<Self::Flavor, Mode = Self::Mode>,
+        M: LatexMode + ModeTransition<D::Mode>,
+    {
+        unsafe { self.write_str(D::CLOSING) }?;
+        Ok(unsafe { self.switch_mode_unchecked() })
+    }
+
+    /// Writes math delimited with `D` using the closure and returns to the current mode.
+    fn write_math<D, F>(self, f: F) -> Result<Self, crate::error::Error>
+    where
+        Self: Sized,
+        Self::Mode: ModeTransition<D::Mode>,
+        D: MathDelimiters<Self::Flavor>,
+        F: FnOnce(Self::WithMode<D::Mode>) -> Result<Self::WithMode<D::Mode>, crate::error::Error>,
+    {
+        let mut w = f(self.open_math::<D>()?)?;
+        unsafe { w.write_str(D::CLOSING) }?;
+        let (nested_writer, features) = w.into_raw_parts();
+        Ok(unsafe { Self::from_raw_parts(nested_writer, features) })
+    }
+
     #[inline(always)]
     fn new(w: Self::NestedWriter) -> Self
     where
@@ -111,28 +209,35 @@ pub trait LatexWriter: UnsafeWrite {
         Self::new(Default::default())
     }
 
+    /// # Safety
+    ///
+    /// What `f` writes to the nested writer must meet the requirements of
+    /// [`UnsafeWrite::write_str`].
     unsafe fn apply_to_nested_writer<F,O>(&mut self, f: F) -> O
     where
         F: Fn(&mut Self::NestedWriter) -> O;
 
+    /// Records that the written LaTeX requires the package, see [`LatexFeatures::require_package`].
+    fn require_package(&mut self, package: Package);
+
     #[inline(always)]
-    fn write_two_dollar_signs(mut self) -> Result<Self::WriteTwoDollarSignsTarget, Error>
+    fn write_two_dollar_signs(
+        mut self,
+    ) -> Result<Self::WithMode<Self::TwoDollarSignsTargetMode>, Error>
     where
         Self: Sized + WriteTwoDollarSignsTargetExt,
     {
         unsafe { self.write_str("$$") }?;
-        let (nested_writer, features) = self.into_raw_parts();
-        Ok(unsafe { <_>::from_raw_parts(nested_writer, features) })
+        Ok(unsafe { self.switch_mode_unchecked() })
     }
 
     #[inline(always)]
-    fn write_dollar_sign(mut self) -> Result<Self::WriteDollarSignTarget, Error>
+    fn write_dollar_sign(mut self) -> Result<Self::WithMode<Self::DollarSignTargetMode>, Error>
     where
         Self: Sized + WriteDollarSignTargetExt,
     {
         unsafe { self.write_str("$") }?;
-        let (nested_writer, features) = self.into_raw_parts();
-        Ok(unsafe { <_>::from_raw_parts(nested_writer, features) })
+        Ok(unsafe { self.switch_mode_unchecked() })
     }
 }
 
@@ -144,6 +249,9 @@ pub struct Writer<Fl, Fe, M, W> {
 }
 
 impl<Fl, Fe, M, W> Writer<Fl, Fe, M, W> {
+    /// # Safety
+    ///
+    /// See [`LatexWriter::from_raw_parts`].
     #[inline(always)]
     pub unsafe fn new(writer: W, features: Fe) -> Self {
         Self {
@@ -162,6 +270,9 @@ where
     M: LatexMode,
     W: core::fmt::Write,
 {
+    /// # Safety
+    ///
+    /// See [`LatexWriter::switch_mode_unchecked`].
     #[inline(always)]
     pub unsafe fn switch_mode_unchecked<Nm: LatexMode>(self) -> Writer<Fl, Fe, Nm, W> {
         Writer {
@@ -172,6 +283,9 @@ where
         }
     }
 
+    /// # Safety
+    ///
+    /// See [`LatexWriter::switch_features_unchecked`].
     #[inline(always)]
     pub unsafe fn switch_features_unchecked<Nf>(self) -> Writer<Fl, Nf, M, W>
     where
@@ -184,6 +298,148 @@ where
             mode: self.mode,
         }
     }
+
+    #[inline(always)]
+    pub fn features(&self) -> &Fe {
+        &self.features
+    }
+
+    /// Converts the writer into the one that records the packages required by the LaTeX
+    /// written from now on.
+    #[inline(always)]
+    pub fn track_packages(self) -> Writer<Fl, RequiredPackages, M, W>
+    where
+        RequiredPackages: From<Fe>,
+    {
+        // Recording package requirements cannot make the written LaTeX invalid
+        unsafe { self.switch_features_unchecked() }
+    }
+
+    /// Converts the writer into the one that writes floating-point numbers in the format.
+    ///
+    /// Call [`Writer::track_packages`] first if the packages should be tracked as well.
+    #[inline(always)]
+    pub fn with_number_format(
+        self,
+        number_format: NumberFormat,
+    ) -> Writer<Fl, WithNumberFormat<Fe>, M, W> {
+        Writer {
+            writer: self.writer,
+            flavor: self.flavor,
+            features: WithNumberFormat::new(self.features, number_format),
+            mode: self.mode,
+        }
+    }
+
+    /// Converts the writer into the one that records the labels and the references to them
+    /// written from now on, see [`fmt::references`](crate::fmt::references).
+    ///
+    /// Call [`Writer::track_packages`] and [`Writer::with_number_format`] first if needed.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline(always)]
+    pub fn track_references(self) -> Writer<Fl, WithReferenceRegistry<Fe>, M, W> {
+        Writer {
+            writer: self.writer,
+            flavor: self.flavor,
+            features: WithReferenceRegistry::new(self.features),
+            mode: self.mode,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<Fl, Fe, M, W> Writer<Fl, WithReferenceRegistry<Fe>, M, W>
+where
+    Fl: LatexFlavorKindExt,
+    Fe: LatexFeatures,
+    M: LatexMode,
+    W: core::fmt::Write,
+{
+    /// Returns the labels and the references written since they have started to be tracked.
+    #[inline(always)]
+    pub fn reference_registry(&self) -> &ReferenceRegistry {
+        self.features.registry()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<Fl, M, W> Writer<Fl, WithReferenceRegistry<RequiredPackages>, M, W>
+where
+    Fl: LatexFlavorKindExt,
+    M: LatexMode,
+    W: core::fmt::Write,
+{
+    /// Returns the packages required by the LaTeX written since the packages have started
+    /// to be tracked.
+    #[inline(always)]
+    pub fn required_packages(&self) -> RequiredPackages {
+        *self.features.inner()
+    }
+}
+
+impl<Fl, M, W> Writer<Fl, RequiredPackages, M, W>
+where
+    Fl: LatexFlavorKindExt,
+    M: LatexMode,
+    W: core::fmt::Write,
+{
+    /// Returns the packages required by the LaTeX written since the packages have started
+    /// to be tracked.
+    #[inline(always)]
+    pub fn required_packages(&self) -> RequiredPackages {
+        self.features
+    }
+}
+
+impl<Fl, Fe, W> Writer<Fl, Fe, VerticalMode, W>
+where
+    Fl: LatexFlavorKindExt,
+    Fe: LatexFeatures,
+    W: core::fmt::Write,
+{
+    /// Starts a paragraph in the main text of the document
+    #[inline(always)]
+    pub fn begin_paragraph(self) -> Writer<Fl, Fe, OuterParagraphMode, W> {
+        // LaTeX starts a paragraph on its own once anything is written in vertical mode
+        unsafe { self.switch_mode_unchecked() }
+    }
+}
+
+impl<Fl, Fe, W> Writer<Fl, Fe, OuterParagraphMode, W>
+where
+    Fl: LatexFlavorKindExt,
+    Fe: LatexFeatures,
+    W: core::fmt::Write,
+{
+    /// Ends the paragraph with a blank line
+    #[inline(always)]
+    pub fn end_paragraph(mut self) -> Result<Writer<Fl, Fe, VerticalMode, W>, Error> {
+        unsafe { self.write_str("\n\n") }?;
+        Ok(unsafe { self.switch_mode_unchecked() })
+    }
+
+    /// Writes inline math delimited with `$` using the closure and returns to the paragraph
+    pub fn write_inline_math<F>(self, f: F) -> Result<Self, crate::error::Error>
+    where
+        F: FnOnce(
+            Writer<Fl, Fe, InlineMathMode, W>,
+        ) -> Result<Writer<Fl, Fe, InlineMathMode, W>, crate::error::Error>,
+    {
+        self.write_in_mode(f)
+    }
+
+    /// Writes display math delimited with `$$` using the closure and returns to the paragraph
+    pub fn write_display_math<F>(self, f: F) -> Result<Self, crate::error::Error>
+    where
+        F: FnOnce(
+            Writer<Fl, Fe, DisplayMathMode, W>,
+        ) -> Result<Writer<Fl, Fe, DisplayMathMode, W>, crate::error::Error>,
+    {
+        self.write_in_mode(f)
+    }
 }
 
 impl<Fl, Fe, M, W> UnsafeWrite for Writer<Fl, Fe, M, W>
@@ -221,11 +477,8 @@ where
     type Features = Fe;
     type Mode = M;
 
-    type DisplayMathWriter = Writer<Fl, Fe, DisplayMathMode, W>;
-
-    type InlineMathWriter = Writer<Fl, Fe, InlineMathMode, W>;
-
-    type InnerParagraphWriter = Writer<Fl, Fe, InnerParagraphMode, W>;
+    type WithMode<Nm: LatexMode> = Writer<Fl, Fe, Nm, W>;
+    type WithFeatures<Nf: LatexFeatures> = Writer<Fl, Nf, M, W>;
 
     #[inline(always)]
     fn into_raw_parts(self) -> (Self::NestedWriter, Self::Features) {
@@ -247,6 +500,11 @@ where
             F: Fn(&mut Self::NestedWriter) -> O {
         f(&mut self.writer)
     }
+
+    #[inline(always)]
+    fn require_package(&mut self, package: Package) {
+        self.features.require_package(package)
+    }
 }
 
 impl<Fl, Fe, W> WriteTwoDollarSignsTargetExt for Writer<Fl, Fe, DisplayMathMode, W>
@@ -255,8 +513,7 @@ where
     Fl: LatexFlavorKindExt,
     Fe: LatexFeatures,
 {
-    #[allow(deprecated)]
-    type WriteTwoDollarSignsTarget = Self::InnerParagraphWriter;
+    type TwoDollarSignsTargetMode = InnerParagraphMode;
 }
 
 impl<Fl, Fe, W> WriteTwoDollarSignsTargetExt for Writer<Fl, Fe, InnerParagraphMode, W>
@@ -265,8 +522,7 @@ where
     Fl: LatexFlavorKindExt,
     Fe: LatexFeatures,
 {
-    #[allow(deprecated)]
-    type WriteTwoDollarSignsTarget = Self::DisplayMathWriter;
+    type TwoDollarSignsTargetMode = DisplayMathMode;
 }
 
 impl<Fl, Fe, W> WriteDollarSignTargetExt for Writer<Fl, Fe, InlineMathMode, W>
@@ -275,8 +531,7 @@ where
     Fl: LatexFlavorKindExt,
     Fe: LatexFeatures,
 {
-    #[allow(deprecated)]
-    type WriteDollarSignTarget = Self::InnerParagraphWriter;
+    type DollarSignTargetMode = InnerParagraphMode;
 }
 
 impl<Fl, Fe, W> WriteDollarSignTargetExt for Writer<Fl, Fe, InnerParagraphMode, W>
@@ -285,8 +540,7 @@ where
     Fl: LatexFlavorKindExt,
     Fe: LatexFeatures,
 {
-    #[allow(deprecated)]
-    type WriteDollarSignTarget = Self::InlineMathWriter;
+    type DollarSignTargetMode = InlineMathMode;
 }
 
 impl<Fe,W> WriteLabel for Writer<MathJax, Fe, DisplayMathMode, W>
@@ -298,20 +552,16 @@ where
         where
         L: Label
     {
-        if label.is_subeq() {
-            // By default, MathJax doesn't support multiple labels so
-            // we fall back to emulating them
-            unsafe { self.write_str("& (") }?;
-            unsafe { self.apply_to_nested_writer(|w| label.write_name(w)) }?;
-            unsafe { self.write_char(')')}
-        } else {
-            unsafe { self.write_str(r"\tag{") }?;
-            unsafe { self.apply_to_nested_writer(|w| label.write_name(w)) }?;
-            unsafe { self.write_str("}") }?;
-            unsafe { self.write_str(r"\label{") }?;
-            unsafe { self.apply_to_nested_writer(|w| label.write_name(w)) }?;
-            unsafe { self.write_str("}") }
-        }
+        // MathJax v3 supports `\tag` in every row of `align` and the other multiline
+        // environments, so subequations get real tags as well
+        unsafe { self.write_str(r"\tag{") }?;
+        unsafe { self.apply_to_nested_writer(|w| label.write_name(w)) }?;
+        unsafe { self.write_str("}") }?;
+        unsafe { self.write_str(r"\label{") }?;
+        unsafe { self.apply_to_nested_writer(|w| label.write_key(w)) }?;
+        unsafe { self.write_str("}") }?;
+        self.features.record_label(label);
+        Ok(())
     }
 }
 
@@ -324,11 +574,14 @@ where
     where
         L: Label
     {
+        self.require_package(Package::Amsmath);
         unsafe { self.write_str(r"\tag{") }?;
         unsafe { self.apply_to_nested_writer(|w| label.write_name(w)) }?;
         unsafe { self.write_str("}") }?;
         unsafe { self.write_str(r"\label{") }?;
-        unsafe { self.apply_to_nested_writer(|w| label.write_name(w)) }?;
-        unsafe { self.write_str("}") }
+        unsafe { self.apply_to_nested_writer(|w| label.write_key(w)) }?;
+        unsafe { self.write_str("}") }?;
+        self.features.record_label(label);
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,15 +1,36 @@
 #![doc = include_str!("../README.md")]
-#![cfg_attr(not(any(doc, test, doctest, feature = "lin_sys")), no_std)]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "adt_const_params", feature(adt_const_params))]
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod buf;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod document;
 pub mod env;
+pub mod error;
+#[cfg(feature = "evcxr")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "evcxr")))]
+pub mod evcxr;
 pub mod fmt;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub mod io;
 pub mod latex_features;
 pub mod latex_flavors;
 pub mod latex_modes;
 pub mod latex_writer;
+#[cfg(feature = "lin_sys")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "lin_sys")))]
 pub mod lin_sys;
+pub mod math_delimiters;
+pub mod matrix;
+pub mod text;
+#[cfg(feature = "hyperref")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "hyperref")))]
 pub mod hyperref;
 
 mod macros {
@@ -29,11 +50,44 @@ mod macros {
                 Try using #[on_format_error(unwrap)] or #[on_format_error(?)]");
         };
 
+        // Collects the tokens up to the closing delimiter and writes them in math mode
+        (@math $strategy:tt $w:ident BackslashParentheses [$($inner:tt)*] "\\)" ; $($tail:tt)*) => {
+            latex_format!(@write_math $strategy $w BackslashParentheses [$($inner)*]);
+            latex_format!(#[on_format_error($strategy)] $w += $($tail)*);
+        };
+        (@math $strategy:tt $w:ident BackslashBrackets [$($inner:tt)*] "\\]" ; $($tail:tt)*) => {
+            latex_format!(@write_math $strategy $w BackslashBrackets [$($inner)*]);
+            latex_format!(#[on_format_error($strategy)] $w += $($tail)*);
+        };
+        (@math $strategy:tt $w:ident EquationStar [$($inner:tt)*] "\\end{equation*}" ; $($tail:tt)*) => {
+            latex_format!(@write_math $strategy $w EquationStar [$($inner)*]);
+            latex_format!(#[on_format_error($strategy)] $w += $($tail)*);
+        };
+        (@math $strategy:tt $w:ident $delims:ident [$($inner:tt)*]) => {
+            compile_error!("The math block is not closed");
+        };
+        (@math $strategy:tt $w:ident $delims:ident [$($inner:tt)*] $next:tt $($tail:tt)*) => {
+            latex_format!(@math $strategy $w $delims [$($inner)* $next] $($tail)*);
+        };
+
+        (@write_math $strategy:tt $w:ident $delims:ident [$($inner:tt)*]) => {
+            let $w = {
+                use $crate::latex_writer::LatexWriter as LW;
+                let res: Result<_, $crate::error::Error> =
+                    <_ as LW>::write_math::<$crate::math_delimiters::$delims, _>($w, |$w| {
+                        latex_format!(#[on_format_error(?)] $w += $($inner)*);
+                        Ok($w)
+                    });
+                latex_format!(@handle_possible_error res $strategy)
+            };
+        };
+
         (#[on_format_error($strategy:tt)] $w:ident += ) => {};
         (#[on_format_error($strategy:tt)] $w:ident += "$$" ; $($tail:tt)*) => {
             let $w = {
                 use $crate::latex_writer::LatexWriter as LW;
-                let res: Result<_, ::core::fmt::Error> = <_ as LW>::write_two_dollar_signs($w);
+                let res: Result<_, $crate::error::Error> = <_ as LW>::write_two_dollar_signs($w)
+                    .map_err($crate::error::Error::from);
                 latex_format!(@handle_possible_error res $strategy)
             };
             latex_format!(#[on_format_error($strategy)] $w += $($tail)*);
@@ -41,16 +95,40 @@ mod macros {
         (#[on_format_error($strategy:tt)] $w:ident += "$" ; $($tail:tt)*) => {
             let $w = {
                 use $crate::latex_writer::LatexWriter as LW;
-                let res: Result<_, ::core::fmt::Error> = <_ as LW>::write_dollar_sign($w);
+                let res: Result<_, $crate::error::Error> = <_ as LW>::write_dollar_sign($w)
+                    .map_err($crate::error::Error::from);
                 latex_format!(@handle_possible_error res $strategy)
             };
             latex_format!(#[on_format_error($strategy)] $w += $($tail)*);
         };
+        (#[on_format_error($strategy:tt)] $w:ident += "\\(" ; $($tail:tt)*) => {
+            latex_format!(@math $strategy $w BackslashParentheses [] $($tail)*);
+        };
+        (#[on_format_error($strategy:tt)] $w:ident += "\\[" ; $($tail:tt)*) => {
+            latex_format!(@math $strategy $w BackslashBrackets [] $($tail)*);
+        };
+        (#[on_format_error($strategy:tt)] $w:ident += "\\begin{equation*}" ; $($tail:tt)*) => {
+            latex_format!(@math $strategy $w EquationStar [] $($tail)*);
+        };
+        (#[on_format_error($strategy:tt)] $w:ident += "\\)" ; $($tail:tt)*) => {
+            compile_error!("\\) does not close a math block");
+        };
+        (#[on_format_error($strategy:tt)] $w:ident += "\\]" ; $($tail:tt)*) => {
+            compile_error!("\\] does not close a math block");
+        };
+        (#[on_format_error($strategy:tt)] $w:ident += "\\end{equation*}" ; $($tail:tt)*) => {
+            compile_error!("\\end{equation*} does not close a math block");
+        };
         (#[on_format_error($strategy:tt)] $w:ident += $str:literal; $($tail:tt)*) => {
             let mut $w = $w;
             let $w = {
-                use $crate::latex_writer::UnsafeWrite;
-                let res: Result<_, ::core::fmt::Error> = unsafe { <_ as UnsafeWrite>::write_str(&mut $w, $str) }
+                use $crate::text::{RawLatex, WriteRawLatex};
+                // String literals are checked with `RawLatex::new`
+                let res: Result<_, $crate::error::Error> = RawLatex::new($str)
+                    .map_err($crate::error::Error::from)
+                    .and_then(|raw| {
+                        <_ as WriteRawLatex>::write_raw(&mut $w, raw).map_err($crate::error::Error::from)
+                    })
                     .map(move |_| $w);
                 latex_format!(@handle_possible_error res $strategy)
             };
@@ -1,6 +1,9 @@
 use crate::{
-    env::LatexEnvironment, latex_features::LatexFeatures, latex_flavors::LatexFlavor,
-    latex_modes::MathLatexMode, latex_writer::LatexWriter,
+    env::LatexEnvironment,
+    latex_features::{LatexFeatures, Package},
+    latex_flavors::LatexFlavor,
+    latex_modes::MathLatexMode,
+    latex_writer::LatexWriter,
 };
 
 pub struct CasesEnvironment;
@@ -13,8 +16,8 @@ where
     W: core::fmt::Write,
     InitW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = W>,
 {
-    type InterWriter = InitW;
-    type OWriter = InitW;
+    type InnerMode = M;
+    const PACKAGE: Option<Package> = Some(Package::Amsmath);
     fn write_name(w: &mut W) -> Result<(), core::fmt::Error> {
         w.write_str("cases")
     }
@@ -1 +1,4 @@
 pub struct OutOfBoundsError;
+
+#[derive(Debug)]
+pub struct InvalidRowOperationError;
@@ -1,9 +1,10 @@
 use nalgebra::{Dim, RawStorage};
 
 use crate::{
+    error::Error,
     fmt::{
         labels::{EqChangeExt, LabelGenerator},
-        FormatAsLabelledDisplayMathBlock, PartialEndofunctionalWriteAsLatex, LatexFormatter,
+        FormatAsLabelledDisplayMathBlock, LatexFormatter,
     },
     latex_features::LatexFeatures,
     latex_flavors::LatexFlavor,
@@ -12,45 +13,53 @@ use crate::{
     lin_sys::{unknowns::Unknowns, LinSys},
 };
 
-use super::CasesLinSysFormatter;
+use super::{
+    AlignatLinSysFormatter, CasesLinSysFormatter, SignAwareCasesLinSysFormatter,
+    SystemeLinSysFormatter,
+};
 
-impl<Fl, Fe, T, R, C, S, U> FormatAsLabelledDisplayMathBlock<Fl, Fe, LinSys<T, R, C, S, U>>
-    for CasesLinSysFormatter
-where
-    Fl: LatexFlavor,
-    Fe: LatexFeatures,
-    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, DisplayMathMode>,
-    R: Dim,
-    C: Dim,
-    S: RawStorage<T, R, C>,
-    U: Unknowns,
-{
-    #[allow(deprecated)]
-    fn format_as_labelled_display_math_block<G, IW, OW, L>(
-        dest: IW,
-        label_gen: &mut G,
-        input: &LinSys<T, R, C, S, U>,
-    ) -> Result<(OW,L), core::fmt::Error>
-    where
-        G: LabelGenerator<Label = L> + EqChangeExt,
-        IW: LatexWriter<
-                Flavor = Fl,
-                Features = Fe,
-                Mode = InnerParagraphMode,
-                NestedWriter = OW::NestedWriter,
-            > + WriteTwoDollarSignsTargetExt,
-        IW::WriteTwoDollarSignsTarget: LatexWriter<Mode = DisplayMathMode>,
-        IW::DisplayMathWriter: WriteTwoDollarSignsTargetExt<WriteTwoDollarSignsTarget = IW>
-            + WriteLabel,
-        OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = InnerParagraphMode>,
-    {
-        let dest = <IW>::write_two_dollar_signs(dest)?;
-        #[allow(deprecated)]
-        let mut dest: <IW as LatexWriter>::DisplayMathWriter
-            = <Self>::fmt(dest, input)?;
-        let label = unsafe { label_gen.write_next_label(&mut dest, G::EQ_CHANGE) }
-            .map_err(|_| core::fmt::Error)?;
-        let dest = dest.write_two_dollar_signs()?;
-        Ok((unsafe { dest.rebuild() }, label))
-    }
+macro_rules! impl_for_formatter {
+    ($formatter:ident) => {
+        impl<Fl, Fe, T, R, C, S, U> FormatAsLabelledDisplayMathBlock<Fl, Fe, LinSys<T, R, C, S, U>>
+            for $formatter
+        where
+            Fl: LatexFlavor,
+            Fe: LatexFeatures,
+            R: Dim,
+            C: Dim,
+            S: RawStorage<T, R, C>,
+            U: Unknowns,
+            Self: LatexFormatter<Fl, Fe, Fe, DisplayMathMode, DisplayMathMode, LinSys<T, R, C, S, U>>,
+        {
+            fn format_as_labelled_display_math_block<G, IW, OW, L>(
+                dest: IW,
+                label_gen: &mut G,
+                input: &LinSys<T, R, C, S, U>,
+            ) -> Result<(OW,L), Error>
+            where
+                G: LabelGenerator<Label = L> + EqChangeExt,
+                Error: From<G::Error>,
+                IW: LatexWriter<
+                        Flavor = Fl,
+                        Features = Fe,
+                        Mode = InnerParagraphMode,
+                        NestedWriter = OW::NestedWriter,
+                    > + WriteTwoDollarSignsTargetExt<TwoDollarSignsTargetMode = DisplayMathMode>,
+                IW::WithMode<DisplayMathMode>:
+                    WriteTwoDollarSignsTargetExt<TwoDollarSignsTargetMode = InnerParagraphMode> + WriteLabel,
+                OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = InnerParagraphMode>,
+            {
+                let dest = <IW>::write_two_dollar_signs(dest)?;
+                let mut dest: IW::WithMode<DisplayMathMode> = <Self>::fmt(dest, input)?;
+                let label = unsafe { label_gen.write_next_label(&mut dest, G::EQ_CHANGE) }?;
+                let dest = dest.write_two_dollar_signs()?;
+                Ok((unsafe { dest.rebuild() }, label))
+            }
+        }
+    };
 }
+
+impl_for_formatter!(CasesLinSysFormatter);
+impl_for_formatter!(SignAwareCasesLinSysFormatter);
+impl_for_formatter!(AlignatLinSysFormatter);
+impl_for_formatter!(SystemeLinSysFormatter);
@@ -1,16 +1,30 @@
-use nalgebra::{Dim, RawStorage};
+use nalgebra::{Dim, Matrix, RawStorage};
 
 use crate::{
     env::LatexEnvironment,
-    fmt::{LatexFormatter, PartialEndofunctionalWriteAsLatex},
-    latex_features::LatexFeatures,
-    latex_flavors::LatexFlavor,
-    latex_modes::MathLatexMode,
+    error::Error,
+    fmt::{coefficient::Coefficient, LatexFormatter, PartialEndofunctionalWriteAsLatex},
+    latex_features::{LatexFeatures, Package},
+    latex_flavors::{AmsLatex, LatexFlavor},
+    latex_modes::{DisplayMathMode, InnerParagraphMode, MathLatexMode, OuterParagraphMode},
     latex_writer::LatexWriter,
     lin_sys::{env::CasesEnvironment, unknowns::Unknowns, LinSys},
+    matrix::{delimiters::Delimiters, fmt::write_matrix_body},
 };
 
-use super::{CasesLinSysFormatter, PlainLinSysFormatter};
+#[cfg(feature = "alloc")]
+use nalgebra::{allocator::Allocator, DefaultAllocator};
+
+#[cfg(feature = "alloc")]
+use super::RowReductionFormatter;
+#[cfg(feature = "alloc")]
+use crate::lin_sys::row_reduction::RowReduction;
+
+use super::{
+    AlignatLinSysFormatter, AugmentedMatrixLinSysFormatter, CasesLinSysFormatter,
+    PlainLinSysFormatter, SignAwareCasesLinSysFormatter,
+    SignAwarePlainLinSysFormatter, SystemeLinSysFormatter,
+};
 
 impl<Fl, Fe, M, T, R, C, S, U> LatexFormatter<Fl, Fe, Fe, M, M, LinSys<T, R, C, S, U>>
     for PlainLinSysFormatter
@@ -24,7 +38,7 @@ where
     S: RawStorage<T, R, C>,
     U: Unknowns,
 {
-    fn fmt<IW, OW>(mut dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, core::fmt::Error>
+    fn fmt<IW, OW>(mut dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, Error>
     where
         IW: crate::latex_writer::LatexWriter<
             Flavor = Fl,
@@ -71,17 +85,384 @@ where
     S: RawStorage<T, R, C>,
     U: Unknowns,
 {
-    fn fmt<IW, OW>(mut dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, core::fmt::Error>
+    fn fmt<IW, OW>(dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, Error>
+    where
+        IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = OW::NestedWriter>,
+        OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+    {
+        let dest: IW =
+            CasesEnvironment::with_env(dest, |dest| PlainLinSysFormatter::fmt(dest, input))?;
+        Ok(unsafe { dest.rebuild() })
+    }
+}
+
+impl<Fl, Fe, M, T, R, C, S, U> LatexFormatter<Fl, Fe, Fe, M, M, LinSys<T, R, C, S, U>>
+    for SignAwarePlainLinSysFormatter
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M> + Coefficient,
+    T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    U: Unknowns,
+{
+    fn fmt<IW, OW>(mut dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, Error>
+    where
+        IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = OW::NestedWriter>,
+        OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+    {
+        let nrows = input.matrix.nrows();
+        let ncols = input.matrix.ncols();
+
+        let ncols_sub1 = match ncols.checked_sub(1) {
+            Some(n) if n != 0 => n,
+            _ => return Ok(unsafe { dest.rebuild() }),
+        };
+        for i in 0..nrows {
+            dest = write_sign_aware_equation(dest, input, i, ncols_sub1)?;
+            if i != nrows - 1 {
+                unsafe { dest.write_str(r"\\") }?;
+            }
+        }
+        Ok(unsafe { dest.rebuild() })
+    }
+}
+
+impl<Fl, Fe, M, T, R, C, S, U> LatexFormatter<Fl, Fe, Fe, M, M, LinSys<T, R, C, S, U>>
+    for SignAwareCasesLinSysFormatter
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M> + Coefficient,
+    T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    U: Unknowns,
+{
+    fn fmt<IW, OW>(dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, Error>
+    where
+        IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = OW::NestedWriter>,
+        OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+    {
+        let dest: IW = CasesEnvironment::with_env(dest, |dest| {
+            SignAwarePlainLinSysFormatter::fmt(dest, input)
+        })?;
+        Ok(unsafe { dest.rebuild() })
+    }
+}
+
+impl<Fl, Fe, M, T, R, C, S, U> LatexFormatter<Fl, Fe, Fe, M, M, LinSys<T, R, C, S, U>>
+    for AlignatLinSysFormatter
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M> + Coefficient,
+    T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    U: Unknowns,
+{
+    fn fmt<IW, OW>(dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, Error>
+    where
+        IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = OW::NestedWriter>,
+        OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+    {
+        let dest = write_alignat(dest, input, "alignedat")?;
+        Ok(unsafe { dest.rebuild() })
+    }
+}
+
+macro_rules! impl_alignat_for_paragraph_mode {
+    ($($mode:ident),+) => {
+        $(
+            impl<Fl, Fe, T, R, C, S, U>
+                LatexFormatter<Fl, Fe, Fe, $mode, $mode, LinSys<T, R, C, S, U>>
+                for AlignatLinSysFormatter
+            where
+                Fl: LatexFlavor,
+                Fe: LatexFeatures,
+                T: PartialEndofunctionalWriteAsLatex<Fl, Fe, DisplayMathMode> + Coefficient,
+                T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, DisplayMathMode>,
+                R: Dim,
+                C: Dim,
+                S: RawStorage<T, R, C>,
+                U: Unknowns,
+            {
+                fn fmt<IW, OW>(dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, Error>
+                where
+                    IW: LatexWriter<
+                        Flavor = Fl,
+                        Features = Fe,
+                        Mode = $mode,
+                        NestedWriter = OW::NestedWriter,
+                    >,
+                    OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = $mode>,
+                {
+                    // `alignat` is a display math block of its own
+                    let dest = unsafe { dest.switch_mode_unchecked::<DisplayMathMode>() };
+                    let dest = write_alignat(dest, input, "alignat")?;
+                    Ok(unsafe { dest.switch_mode_unchecked::<$mode>().rebuild() })
+                }
+            }
+        )+
+    };
+}
+
+impl_alignat_for_paragraph_mode!(OuterParagraphMode, InnerParagraphMode);
+
+// Writes the linear system inside of the environment, which is either `alignat` or `alignedat`.
+fn write_alignat<Fl, Fe, M, T, R, C, S, U, W>(
+    mut dest: W,
+    input: &LinSys<T, R, C, S, U>,
+    env: &str,
+) -> Result<W, Error>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M> + Coefficient,
+    T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    U: Unknowns,
+    W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+{
+    let nrows = input.matrix.nrows();
+    let ncols = input.matrix.ncols();
+
+    let ncols_sub1 = match ncols.checked_sub(1) {
+        Some(n) if n != 0 => n,
+        _ => return Ok(dest),
+    };
+    dest.require_package(Package::Amsmath);
+    // Every unknown takes a column for the term and a column for the preceding
+    // operator, and the right-hand side takes a column for the equality sign and
+    // a column for the constant term.
+    unsafe { dest.write_fmt(format_args!(r"\begin{{{}}}{{{}}}", env, ncols)) }?;
+    for i in 0..nrows {
+        let leading_term_idx = (0..ncols_sub1).find(|&j| !input.matrix[(i, j)].is_zero());
+        for j in 0..ncols_sub1 {
+            let coeff = &input.matrix[(i, j)];
+            let is_leading = leading_term_idx == Some(j);
+            if j != 0 {
+                unsafe { dest.write_char('&') }?;
+                if !coeff.is_zero() && !is_leading {
+                    let op = if coeff.is_negative() { "{}-{}" } else { "{}+{}" };
+                    unsafe { dest.write_str(op) }?;
+                }
+                unsafe { dest.write_char('&') }?;
+            }
+            if coeff.is_zero() {
+                if leading_term_idx.is_none() && j == ncols_sub1 - 1 {
+                    unsafe { dest.write_char('0') }?;
+                }
+                continue;
+            }
+            if is_leading && coeff.is_negative() {
+                unsafe { dest.write_char('-') }?;
+            }
+            let abs = coeff.abs();
+            if !abs.is_one() {
+                dest = abs.partial_endofunctional_write_as_latex(dest)?;
+            }
+            unsafe { input.unknowns.write_ith_unchecked(&mut dest, j) }?;
+        }
+        unsafe { dest.write_str("&{}={}&") }?;
+        dest = input.matrix[(i, ncols_sub1)].partial_endofunctional_write_as_latex(dest)?;
+        if i != nrows - 1 {
+            unsafe { dest.write_str(r"\\") }?;
+        }
+    }
+    unsafe { dest.write_fmt(format_args!(r"\end{{{}}}", env)) }?;
+    Ok(dest)
+}
+
+impl<Fe, M, T, R, C, S, U> LatexFormatter<AmsLatex, Fe, Fe, M, M, LinSys<T, R, C, S, U>>
+    for SystemeLinSysFormatter
+where
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<AmsLatex, Fe, M> + Coefficient,
+    T::Abs: PartialEndofunctionalWriteAsLatex<AmsLatex, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    U: Unknowns,
+{
+    fn fmt<IW, OW>(mut dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, Error>
+    where
+        IW: LatexWriter<
+            Flavor = AmsLatex,
+            Features = Fe,
+            Mode = M,
+            NestedWriter = OW::NestedWriter,
+        >,
+        OW: LatexWriter<Flavor = AmsLatex, Features = Fe, Mode = M>,
+    {
+        let nrows = input.matrix.nrows();
+        let ncols = input.matrix.ncols();
+
+        let ncols_sub1 = match ncols.checked_sub(1) {
+            Some(n) if n != 0 => n,
+            _ => return Ok(unsafe { dest.rebuild() }),
+        };
+        dest.require_package(Package::Systeme);
+        unsafe { dest.write_str(r"\systeme{") }?;
+        for i in 0..nrows {
+            dest = write_sign_aware_equation(dest, input, i, ncols_sub1)?;
+            if i != nrows - 1 {
+                unsafe { dest.write_char(',') }?;
+            }
+        }
+        unsafe { dest.write_char('}') }?;
+        Ok(unsafe { dest.rebuild() })
+    }
+}
+
+impl<Fl, Fe, M, T, R, C, S, U, D> LatexFormatter<Fl, Fe, Fe, M, M, LinSys<T, R, C, S, U>>
+    for AugmentedMatrixLinSysFormatter<D>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    U: Unknowns,
+    D: Delimiters,
+{
+    fn fmt<IW, OW>(mut dest: IW, input: &LinSys<T, R, C, S, U>) -> Result<OW, Error>
+    where
+        IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = OW::NestedWriter>,
+        OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+    {
+        dest = write_augmented_matrix::<D, _, _, _, _, _, _, _, _>(dest, &input.matrix)?;
+        Ok(unsafe { dest.rebuild() })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Fl, Fe, M, T, R, C, U, D> LatexFormatter<Fl, Fe, Fe, M, M, RowReduction<T, R, C, U>>
+    for RowReductionFormatter<D>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M> + Coefficient,
+    T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    U: Unknowns,
+    D: Delimiters,
+    DefaultAllocator: Allocator<T, R, C>,
+{
+    fn fmt<IW, OW>(mut dest: IW, input: &RowReduction<T, R, C, U>) -> Result<OW, Error>
     where
         IW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = OW::NestedWriter>,
         OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
     {
-        dest = CasesEnvironment::write_opening_tag(dest)?;
-        dest = PlainLinSysFormatter::fmt(dest, input)?;
-        dest = CasesEnvironment::write_closing_tag(dest)?;
+        if !input.steps().is_empty() {
+            // `\overset` is provided by `amsmath`
+            dest.require_package(Package::Amsmath);
+        }
+        dest = write_augmented_matrix::<D, _, _, _, _, _, _, _, _>(dest, input.initial())?;
+        for step in input.steps() {
+            unsafe { dest.write_str(r"\overset{") }?;
+            dest = step.operation.partial_endofunctional_write_as_latex(dest)?;
+            unsafe { dest.write_str(r"}{\sim}") }?;
+            dest = write_augmented_matrix::<D, _, _, _, _, _, _, _, _>(dest, &step.matrix)?;
+        }
         Ok(unsafe { dest.rebuild() })
     }
 }
+
+// Writes the i-th equation of the linear system the way it is written by hand,
+// e.g. `x_1-3x_2=5` instead of `1x_1+-3x_2+0x_3=5`.
+fn write_sign_aware_equation<Fl, Fe, M, T, R, C, S, U, W>(
+    mut dest: W,
+    input: &LinSys<T, R, C, S, U>,
+    i: usize,
+    ncols_sub1: usize,
+) -> Result<W, Error>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M> + Coefficient,
+    T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    U: Unknowns,
+    W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+{
+    let mut is_lhs_empty = true;
+    for j in 0..ncols_sub1 {
+        let coeff = &input.matrix[(i, j)];
+        if coeff.is_zero() {
+            continue;
+        }
+        if coeff.is_negative() {
+            unsafe { dest.write_char('-') }?;
+        } else if !is_lhs_empty {
+            unsafe { dest.write_char('+') }?;
+        }
+        let abs = coeff.abs();
+        if !abs.is_one() {
+            dest = abs.partial_endofunctional_write_as_latex(dest)?;
+        }
+        unsafe { input.unknowns.write_ith_unchecked(&mut dest, j) }?;
+        is_lhs_empty = false;
+    }
+    if is_lhs_empty {
+        unsafe { dest.write_char('0') }?;
+    }
+    unsafe { dest.write_char('=') }?;
+    input.matrix[(i, ncols_sub1)].partial_endofunctional_write_as_latex(dest)
+}
+
+// Writes the augmented matrix as an `array` environment where the column of
+// constant terms is separated by a vertical bar.
+fn write_augmented_matrix<D, Fl, Fe, M, T, R, C, S, W>(
+    mut dest: W,
+    matrix: &Matrix<T, R, C, S>,
+) -> Result<W, Error>
+where
+    D: Delimiters,
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+{
+    let ncols_sub1 = matrix
+        .ncols()
+        .checked_sub(1)
+        .ok_or(Error::DimensionMismatch { expected: 1, found: 0 })?;
+    unsafe { dest.write_str(D::LEFT) }?;
+    unsafe { dest.write_str(r"\begin{array}{") }?;
+    for _ in 0..ncols_sub1 {
+        unsafe { dest.write_char('c') }?;
+    }
+    unsafe { dest.write_str("|c}") }?;
+    dest = write_matrix_body(dest, matrix)?;
+    unsafe { dest.write_str(r"\end{array}") }?;
+    unsafe { dest.write_str(D::RIGHT) }?;
+    Ok(dest)
+}
+
 //impl<IM, OM, T, R, C, S, U> LatexFormatter<IM, OM, LinSys<T, R, C, S, U>> for CasesLinSysFormatter
 //where
 //    IM: CategorizedLatexModeKindExt,
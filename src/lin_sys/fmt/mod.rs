@@ -1,11 +1,284 @@
-//! A module offering a number of [LaTeX] formatters for entities from [`nalgebra_linsys`].
+//! A module offering a number of [LaTeX] formatters for [linear systems](crate::lin_sys::LinSys).
 //!
-//! [`nalgebra_linsys`]: https://crates.io/crates/nalgebra_linsys
 //! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
 
+use core::marker::PhantomData;
+
+use crate::matrix::delimiters::Parentheses;
+
 mod impl_format_as_labelled_display_math_block;
 mod impl_latex_formatter;
 
 pub struct PlainLinSysFormatter;
 
 pub struct CasesLinSysFormatter;
+
+/// Formatter that writes linear equations the way they are written by hand: the terms with zero
+/// coefficients are omitted, unit coefficients are dropped, and the signs of negative coefficients
+/// replace the plus signs, e.g. `x_1-3x_2=5` instead of `1x_1+-3x_2+0x_3=5`.
+///
+/// If all coefficients on the left-hand side are zero, the left-hand side is written as `0`.
+///
+/// The coefficients must implement [`Coefficient`][crate::fmt::coefficient::Coefficient].
+///
+/// # Example
+///
+/// ```
+/// use nalgebra::{matrix, Const};
+/// use nalgebra_latex::{
+///     fmt::LatexFormatter,
+///     latex_features::NoFeatures,
+///     latex_flavors::AmsLatex,
+///     latex_modes::InlineMathMode,
+///     latex_writer::{LatexWriter, Writer},
+///     lin_sys::{
+///         fmt::{SignAwareCasesLinSysFormatter, SignAwarePlainLinSysFormatter},
+///         numbering::Numbering,
+///         unknowns::SingleLetterBoldfaceVecOfUnknowns,
+///         LinSys,
+///     },
+/// };
+///
+/// type W = Writer<AmsLatex, NoFeatures, InlineMathMode, String>;
+///
+/// let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<3>);
+/// let lin_sys = LinSys::new(
+///     matrix![
+///         1, -3, 0, 5;
+///         -1, 2, -1, 0;
+///         0, 0, 0, 0
+///     ],
+///     unknowns,
+/// )
+/// .unwrap();
+///
+/// let w: W = SignAwarePlainLinSysFormatter::fmt(W::default(), &lin_sys).unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(s, r"x_{1}-3x_{2}=5\\-x_{1}+2x_{2}-x_{3}=0\\0=0");
+///
+/// let w: W = SignAwareCasesLinSysFormatter::fmt(W::default(), &lin_sys).unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(
+///     s,
+///     r"\begin{cases}x_{1}-3x_{2}=5\\-x_{1}+2x_{2}-x_{3}=0\\0=0\end{cases}"
+/// );
+/// ```
+pub struct SignAwarePlainLinSysFormatter;
+
+/// Same as [`SignAwarePlainLinSysFormatter`] but the equations are put inside of the `cases`
+/// environment.
+pub struct SignAwareCasesLinSysFormatter;
+
+/// Formatter that aligns the terms of linear equations column by column, so that the terms with
+/// the same unknown, the operators, and the equality signs line up vertically.
+///
+/// In paragraph modes, the equations are written inside of the `alignat` environment, which is
+/// a display math block of its own. In math modes, e.g. inside of `$$`, they are written inside of
+/// the `alignedat` environment, which is the variant of `alignat` for math mode. Every term
+/// and every operator occupies its own column. The terms with zero coefficients leave their columns
+/// empty, and otherwise the terms are written the same way as by [`SignAwarePlainLinSysFormatter`].
+///
+/// # Example
+///
+/// ```
+/// use nalgebra::{matrix, Const};
+/// use nalgebra_latex::{
+///     fmt::{labels::Counters, FormatAsLabelledDisplayMathBlock, LatexFormatter},
+///     latex_features::NoFeatures,
+///     latex_flavors::MathJax,
+///     latex_modes::{DisplayMathMode, InnerParagraphMode},
+///     latex_writer::{LatexWriter, Writer},
+///     lin_sys::{
+///         fmt::AlignatLinSysFormatter,
+///         numbering::Numbering,
+///         unknowns::SingleLetterBoldfaceVecOfUnknowns,
+///         LinSys,
+///     },
+/// };
+///
+/// type W = Writer<MathJax, NoFeatures, DisplayMathMode, String>;
+///
+/// let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<3>);
+/// let lin_sys = LinSys::new(
+///     matrix![
+///         2, 1, -1, 8;
+///         -3, 0, 2, -11
+///     ],
+///     unknowns,
+/// )
+/// .unwrap();
+///
+/// let w: W = AlignatLinSysFormatter::fmt(W::default(), &lin_sys).unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(
+///     s,
+///     concat!(
+///         r"\begin{alignedat}{4}",
+///         r"2x_{1}&{}+{}&x_{2}&{}-{}&x_{3}&{}={}&8\\",
+///         r"-3x_{1}&&&{}+{}&2x_{3}&{}={}&-11",
+///         r"\end{alignedat}",
+///     )
+/// );
+///
+/// let w = Writer::<MathJax, NoFeatures, InnerParagraphMode, String>::default();
+/// let w: Writer<_, _, InnerParagraphMode, _> = AlignatLinSysFormatter::fmt(w, &lin_sys).unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(
+///     s,
+///     concat!(
+///         r"\begin{alignat}{4}",
+///         r"2x_{1}&{}+{}&x_{2}&{}-{}&x_{3}&{}={}&8\\",
+///         r"-3x_{1}&&&{}+{}&2x_{3}&{}={}&-11",
+///         r"\end{alignat}",
+///     )
+/// );
+///
+/// let mut counters = Counters::new();
+/// let (w, _label): (Writer<MathJax, NoFeatures, InnerParagraphMode, String>, _) =
+///     AlignatLinSysFormatter::format_as_labelled_display_math_block(
+///         Writer::default(),
+///         &mut counters,
+///         &lin_sys,
+///     )
+///     .unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(
+///     s,
+///     concat!(
+///         r"$$\begin{alignedat}{4}",
+///         r"2x_{1}&{}+{}&x_{2}&{}-{}&x_{3}&{}={}&8\\",
+///         r"-3x_{1}&&&{}+{}&2x_{3}&{}={}&-11",
+///         r"\end{alignedat}\tag{1}\label{1}$$",
+///     )
+/// );
+/// ```
+pub struct AlignatLinSysFormatter;
+
+/// Formatter that writes linear equations with the [`\systeme`][systeme] command from the
+/// `systeme` package, which aligns the terms of the equations by itself.
+///
+/// Since the package is not available in [MathJax], the formatter supports only the
+/// [`AmsLatex`][crate::latex_flavors::AmsLatex] flavor. The terms are written the same way as by
+/// [`SignAwarePlainLinSysFormatter`].
+///
+/// # Example
+///
+/// ```
+/// use nalgebra::{matrix, Const};
+/// use nalgebra_latex::{
+///     fmt::LatexFormatter,
+///     latex_features::NoFeatures,
+///     latex_flavors::AmsLatex,
+///     latex_modes::DisplayMathMode,
+///     latex_writer::{LatexWriter, Writer},
+///     lin_sys::{
+///         fmt::SystemeLinSysFormatter,
+///         unknowns::SingleLetterBoldfaceVecOfDistinctSingleLetterUnknowns,
+///         LinSys,
+///     },
+/// };
+///
+/// type W = Writer<AmsLatex, NoFeatures, DisplayMathMode, String>;
+///
+/// let unknowns = SingleLetterBoldfaceVecOfDistinctSingleLetterUnknowns::new('v', ['x', 'y']);
+/// let lin_sys = LinSys::new(matrix![1, -1, 0; 2, 3, 5], unknowns).unwrap();
+///
+/// let w: W = SystemeLinSysFormatter::fmt(W::default(), &lin_sys).unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(s, r"\systeme{x-y=0,2x+3y=5}");
+/// ```
+///
+/// [systeme]: https://ctan.org/pkg/systeme
+/// [MathJax]: https://www.mathjax.org/
+pub struct SystemeLinSysFormatter;
+
+/// Formatter of the augmented matrix of a [`LinSys`][super::LinSys] as an `array` environment
+/// where the column of constant terms is separated by a vertical bar.
+///
+/// The delimiters around the array are chosen with the type parameter, which should be
+/// one of the types from [`matrix::delimiters`][crate::matrix::delimiters].
+///
+/// # Example
+///
+/// ```
+/// use nalgebra::{matrix, Const};
+/// use nalgebra_latex::{
+///     fmt::LatexFormatter,
+///     latex_features::NoFeatures,
+///     latex_flavors::AmsLatex,
+///     latex_modes::InlineMathMode,
+///     latex_writer::{LatexWriter, Writer},
+///     lin_sys::{
+///         fmt::AugmentedMatrixLinSysFormatter,
+///         numbering::Numbering,
+///         unknowns::SingleLetterBoldfaceVecOfUnknowns,
+///         LinSys,
+///     },
+///     matrix::delimiters::Brackets,
+/// };
+///
+/// type W = Writer<AmsLatex, NoFeatures, InlineMathMode, String>;
+///
+/// let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<2>);
+/// let lin_sys = LinSys::new(matrix![1, 2, 3; 4, 5, 6], unknowns).unwrap();
+///
+/// let w: W = <AugmentedMatrixLinSysFormatter>::fmt(W::default(), &lin_sys).unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(s, r"\left(\begin{array}{cc|c}1&2&3\\4&5&6\end{array}\right)");
+///
+/// let w: W = AugmentedMatrixLinSysFormatter::<Brackets>::fmt(W::default(), &lin_sys).unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(s, r"\left[\begin{array}{cc|c}1&2&3\\4&5&6\end{array}\right]");
+/// ```
+pub struct AugmentedMatrixLinSysFormatter<D = Parentheses>(PhantomData<D>);
+
+/// Formatter of a [`RowReduction`][super::row_reduction::RowReduction] as a sequence of augmented
+/// matrices joined by `\sim`, each annotated with the row operation that produced the next matrix.
+///
+/// The augmented matrices are written the same way as by [`AugmentedMatrixLinSysFormatter`] with
+/// the same choice of delimiters.
+///
+/// # Example
+///
+/// ```
+/// use nalgebra::{matrix, Const};
+/// use nalgebra_latex::{
+///     fmt::LatexFormatter,
+///     latex_features::NoFeatures,
+///     latex_flavors::MathJax,
+///     latex_modes::DisplayMathMode,
+///     latex_writer::{LatexWriter, Writer},
+///     lin_sys::{
+///         fmt::RowReductionFormatter,
+///         numbering::Numbering,
+///         row_reduction::{RowOperation, RowReduction},
+///         unknowns::SingleLetterBoldfaceVecOfUnknowns,
+///         LinSys,
+///     },
+///     matrix::delimiters::Brackets,
+/// };
+///
+/// type W = Writer<MathJax, NoFeatures, DisplayMathMode, String>;
+///
+/// let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<1>);
+/// let lin_sys = LinSys::new(matrix![1.0, 2.0; 3.0, 9.0], unknowns).unwrap();
+///
+/// let mut reduction = RowReduction::new(lin_sys);
+/// reduction
+///     .apply(RowOperation::AddScaled { target: 1, source: 0, factor: -3.0 })
+///     .unwrap();
+///
+/// let w: W = RowReductionFormatter::<Brackets>::fmt(W::default(), &reduction).unwrap();
+/// let (s, _no_features) = w.into_raw_parts();
+/// assert_eq!(
+///     s,
+///     concat!(
+///         r"\left[\begin{array}{c|c}1&2\\3&9\end{array}\right]",
+///         r"\overset{R_{2}\leftarrow R_{2}-3R_{1}}{\sim}",
+///         r"\left[\begin{array}{c|c}1&2\\0&3\end{array}\right]",
+///     )
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct RowReductionFormatter<D = Parentheses>(PhantomData<D>);
@@ -4,6 +4,9 @@ pub mod env;
 pub mod err;
 pub mod fmt;
 pub mod numbering;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod row_reduction;
 pub mod unknowns;
 
 pub struct LinSys<T, R, C, S, U>
@@ -31,13 +34,10 @@ where
     S: RawStorage<T, R, C>,
 {
     pub fn new(mrls: Matrix<T, R, C, S>, unknowns: U) -> Option<Self> {
-        match unknowns.len() {
-            Ok(len) => {
-                if len != mrls.ncols().checked_sub(1)? {
-                    return None;
-                }
+        if let Ok(len) = unknowns.len() {
+            if len != mrls.ncols().checked_sub(1)? {
+                return None;
             }
-            _ => (),
         };
         Some(LinSys {
             matrix: mrls,
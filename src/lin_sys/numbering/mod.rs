@@ -1,4 +1,4 @@
-//! Module with temporary solution for numbering to support formatting entities from [`nalgebra_linsys`].
+//! Module with temporary solution for numbering to support formatting [linear systems](crate::lin_sys::LinSys).
 //!
 //! # Notes
 //!
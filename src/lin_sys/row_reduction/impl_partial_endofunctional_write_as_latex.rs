@@ -0,0 +1,66 @@
+use crate::error::Error;
+
+use super::RowOperation;
+use crate::{
+    fmt::{coefficient::Coefficient, PartialEndofunctionalWriteAsLatex},
+    latex_features::LatexFeatures,
+    latex_flavors::LatexFlavor,
+    latex_modes::MathLatexMode,
+    latex_writer::LatexWriter,
+};
+
+impl<Fl, Fe, M, T> PartialEndofunctionalWriteAsLatex<Fl, Fe, M> for RowOperation<T>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M> + Coefficient,
+    T::Abs: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+{
+    fn partial_endofunctional_write_as_latex<W, NW>(&self, mut dest: W) -> Result<W, Error>
+    where
+        NW: core::fmt::Write,
+        W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = NW>,
+    {
+        match self {
+            RowOperation::Swap(i, j) => unsafe {
+                dest.write_fmt(format_args!(
+                    r"R_{{{}}}\leftrightarrow R_{{{}}}",
+                    i + 1,
+                    j + 1
+                ))?;
+            },
+            RowOperation::Scale(i, factor) => {
+                unsafe { dest.write_fmt(format_args!(r"R_{{{}}}\leftarrow ", i + 1)) }?;
+                if factor.is_negative() {
+                    unsafe { dest.write_char('-') }?;
+                }
+                let abs = factor.abs();
+                if !abs.is_one() {
+                    dest = abs.partial_endofunctional_write_as_latex(dest)?;
+                }
+                unsafe { dest.write_fmt(format_args!("R_{{{}}}", i + 1)) }?;
+            }
+            RowOperation::AddScaled {
+                target,
+                source,
+                factor,
+            } => {
+                unsafe {
+                    dest.write_fmt(format_args!(
+                        r"R_{{{}}}\leftarrow R_{{{}}}",
+                        target + 1,
+                        target + 1
+                    ))
+                }?;
+                unsafe { dest.write_char(if factor.is_negative() { '-' } else { '+' }) }?;
+                let abs = factor.abs();
+                if !abs.is_one() {
+                    dest = abs.partial_endofunctional_write_as_latex(dest)?;
+                }
+                unsafe { dest.write_fmt(format_args!("R_{{{}}}", source + 1)) }?;
+            }
+        };
+        Ok(dest)
+    }
+}
@@ -0,0 +1,286 @@
+//! Module with [elementary row operations] and step-by-step [row reduction] of linear systems.
+//!
+//! A [`RowReduction`] records every applied [`RowOperation`] together with the augmented matrix
+//! it produces, so that the whole reduction can be written with
+//! [`RowReductionFormatter`][crate::lin_sys::fmt::RowReductionFormatter]. The row operations can
+//! be applied one by one with [`RowReduction::apply`] or found automatically with
+//! [`RowReduction::reduce_to_rref`].
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra::{matrix, Const};
+//! use nalgebra_latex::lin_sys::{
+//!     numbering::Numbering,
+//!     row_reduction::{RowOperation, RowReduction},
+//!     unknowns::SingleLetterBoldfaceVecOfUnknowns,
+//!     LinSys,
+//! };
+//!
+//! let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<2>);
+//! let lin_sys = LinSys::new(matrix![0.0, 2.0, 4.0; 1.0, 1.0, 3.0], unknowns).unwrap();
+//!
+//! let mut reduction = RowReduction::new(lin_sys);
+//! reduction.reduce_to_rref().unwrap();
+//! assert_eq!(
+//!     reduction.steps().iter().map(|step| &step.operation).collect::<Vec<_>>(),
+//!     [
+//!         &RowOperation::Swap(0, 1),
+//!         &RowOperation::Scale(1, 0.5),
+//!         &RowOperation::AddScaled { target: 0, source: 1, factor: -1.0 },
+//!     ]
+//! );
+//! assert_eq!(reduction.current(), &matrix![1.0, 0.0, 1.0; 0.0, 1.0, 2.0]);
+//!
+//! // 49.0 * (1.0 / 49.0) is not exactly 1.0 but 49.0 is still a valid pivot
+//! let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<2>);
+//! let lin_sys = LinSys::new(matrix![49.0, 0.0, 49.0; 0.0, 1.0, 2.0], unknowns).unwrap();
+//! let mut reduction = RowReduction::new(lin_sys);
+//! reduction.reduce_to_rref().unwrap();
+//! assert_eq!(
+//!     reduction.steps().iter().map(|step| &step.operation).collect::<Vec<_>>(),
+//!     [&RowOperation::Scale(0, 1.0 / 49.0)]
+//! );
+//!
+//! // Integers are not a field, so the pivot 2 cannot be scaled to 1
+//! let unknowns = SingleLetterBoldfaceVecOfUnknowns::<_, { Numbering::OneBased }>::new('x', Const::<2>);
+//! let lin_sys = LinSys::new(matrix![2, 1, 3; 4, 1, 5], unknowns).unwrap();
+//! let mut reduction = RowReduction::new(lin_sys);
+//! assert!(reduction.reduce_to_rref().is_err());
+//! assert!(reduction.steps().is_empty());
+//! ```
+//!
+//! [elementary row operations]: https://en.wikipedia.org/wiki/Elementary_matrix#Elementary_row_operations
+//! [row reduction]: https://en.wikipedia.org/wiki/Gaussian_elimination
+
+use alloc::vec::Vec;
+use core::ops::Neg;
+
+use nalgebra::{
+    allocator::Allocator, ClosedAdd, ClosedDiv, ClosedMul, DefaultAllocator, Dim, OMatrix, Scalar,
+    Storage,
+};
+use num_traits::One;
+
+use super::{err::InvalidRowOperationError, unknowns::Unknowns, LinSys};
+use crate::fmt::coefficient::Coefficient;
+
+mod impl_partial_endofunctional_write_as_latex;
+
+/// An [elementary row operation] over the rows with the given zero-based indices.
+///
+/// [elementary row operation]: https://en.wikipedia.org/wiki/Elementary_matrix#Elementary_row_operations
+#[derive(Clone, PartialEq, Debug)]
+pub enum RowOperation<T> {
+    /// Swapping of two rows, i.e. `R_i \leftrightarrow R_j`
+    Swap(usize, usize),
+    /// Multiplication of a row by a non-zero factor, i.e. `R_i \leftarrow cR_i`
+    Scale(usize, T),
+    /// Addition of a multiple of the source row to the target row, i.e. `R_i \leftarrow R_i + cR_j`
+    AddScaled {
+        target: usize,
+        source: usize,
+        factor: T,
+    },
+}
+
+/// The result of applying a [`RowOperation`] during a [`RowReduction`].
+pub struct RowReductionStep<T, R, C>
+where
+    R: Dim,
+    C: Dim,
+    DefaultAllocator: Allocator<T, R, C>,
+{
+    /// The applied row operation
+    pub operation: RowOperation<T>,
+    /// The augmented matrix after the row operation
+    pub matrix: OMatrix<T, R, C>,
+}
+
+/// The record of [elementary row operations] applied to the augmented matrix of a [`LinSys`].
+///
+/// [elementary row operations]: https://en.wikipedia.org/wiki/Elementary_matrix#Elementary_row_operations
+pub struct RowReduction<T, R, C, U>
+where
+    R: Dim,
+    C: Dim,
+    U: Unknowns,
+    DefaultAllocator: Allocator<T, R, C>,
+{
+    initial: OMatrix<T, R, C>,
+    steps: Vec<RowReductionStep<T, R, C>>,
+    unknowns: U,
+}
+
+impl<T, R, C, U> RowReduction<T, R, C, U>
+where
+    T: Scalar,
+    R: Dim,
+    C: Dim,
+    U: Unknowns,
+    DefaultAllocator: Allocator<T, R, C>,
+{
+    pub fn new<S>(lin_sys: LinSys<T, R, C, S, U>) -> Self
+    where
+        S: Storage<T, R, C>,
+    {
+        Self {
+            initial: lin_sys.matrix.clone_owned(),
+            steps: Vec::new(),
+            unknowns: lin_sys.unknowns,
+        }
+    }
+}
+
+impl<T, R, C, U> RowReduction<T, R, C, U>
+where
+    R: Dim,
+    C: Dim,
+    U: Unknowns,
+    DefaultAllocator: Allocator<T, R, C>,
+{
+    /// The augmented matrix before any row operations
+    pub fn initial(&self) -> &OMatrix<T, R, C> {
+        &self.initial
+    }
+
+    /// The applied row operations together with the augmented matrices they produced
+    pub fn steps(&self) -> &[RowReductionStep<T, R, C>] {
+        &self.steps
+    }
+
+    /// The augmented matrix after all applied row operations
+    pub fn current(&self) -> &OMatrix<T, R, C> {
+        match self.steps.last() {
+            Some(step) => &step.matrix,
+            None => &self.initial,
+        }
+    }
+
+    /// Converts the record into the linear system after all applied row operations
+    pub fn into_lin_sys(
+        self,
+    ) -> LinSys<T, R, C, <DefaultAllocator as Allocator<T, R, C>>::Buffer, U> {
+        let matrix = match self.steps.into_iter().last() {
+            Some(step) => step.matrix,
+            None => self.initial,
+        };
+        LinSys {
+            matrix,
+            unknowns: self.unknowns,
+        }
+    }
+}
+
+impl<T, R, C, U> RowReduction<T, R, C, U>
+where
+    T: Scalar + Coefficient + ClosedAdd + ClosedMul,
+    R: Dim,
+    C: Dim,
+    U: Unknowns,
+    DefaultAllocator: Allocator<T, R, C>,
+{
+    /// Applies the row operation to the current augmented matrix and records the result.
+    ///
+    /// Row operations that are not elementary, e.g. scaling by zero or adding a multiple of
+    /// a row to itself, as well as row operations with out-of-bounds indices are rejected.
+    pub fn apply(&mut self, operation: RowOperation<T>) -> Result<(), InvalidRowOperationError> {
+        let nrows = self.current().nrows();
+        let mut matrix = self.current().clone_owned();
+        match &operation {
+            RowOperation::Swap(i, j) => {
+                if *i >= nrows || *j >= nrows || i == j {
+                    return Err(InvalidRowOperationError);
+                }
+                matrix.swap_rows(*i, *j);
+            }
+            RowOperation::Scale(i, factor) => {
+                if *i >= nrows || factor.is_zero() {
+                    return Err(InvalidRowOperationError);
+                }
+                for entry in matrix.row_mut(*i).iter_mut() {
+                    *entry = entry.clone() * factor.clone();
+                }
+            }
+            RowOperation::AddScaled {
+                target,
+                source,
+                factor,
+            } => {
+                if *target >= nrows || *source >= nrows || target == source || factor.is_zero() {
+                    return Err(InvalidRowOperationError);
+                }
+                for j in 0..matrix.ncols() {
+                    let addend = matrix[(*source, j)].clone() * factor.clone();
+                    matrix[(*target, j)] += addend;
+                }
+            }
+        };
+        self.steps.push(RowReductionStep { operation, matrix });
+        Ok(())
+    }
+}
+
+impl<T, R, C, U> RowReduction<T, R, C, U>
+where
+    T: Scalar + Coefficient + ClosedAdd + ClosedMul + ClosedDiv + Neg<Output = T> + One,
+    R: Dim,
+    C: Dim,
+    U: Unknowns,
+    DefaultAllocator: Allocator<T, R, C>,
+{
+    /// Brings the current augmented matrix to the [reduced row echelon form] with
+    /// [Gauss-Jordan elimination] and records every row operation.
+    ///
+    /// # Notes
+    ///
+    /// The type of the entries is expected to behave like a [field], e.g. like `f64` or
+    /// a rational number. The first row with a non-zero entry is chosen as the pivot row,
+    /// which is how the reduction is usually done by hand but which is not numerically stable.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`InvalidRowOperationError`] if the inverse of a pivot is zero, e.g. `1 / 2`
+    /// for the pivot `2` of an integer matrix, or a row operation cannot be applied.
+    /// The row operations applied before the failure remain recorded.
+    ///
+    /// [reduced row echelon form]: https://en.wikipedia.org/wiki/Row_echelon_form#Reduced_row_echelon_form
+    /// [Gauss-Jordan elimination]: https://en.wikipedia.org/wiki/Gaussian_elimination
+    /// [field]: https://en.wikipedia.org/wiki/Field_(mathematics)
+    pub fn reduce_to_rref(&mut self) -> Result<(), InvalidRowOperationError> {
+        let (nrows, ncols) = self.current().shape();
+        let mut pivot_row = 0;
+        for j in 0..ncols {
+            if pivot_row == nrows {
+                break;
+            }
+            let i = match (pivot_row..nrows).find(|&i| !self.current()[(i, j)].is_zero()) {
+                Some(i) => i,
+                None => continue,
+            };
+            let pivot = self.current()[(i, j)].clone();
+            let inverse = T::one() / pivot.clone();
+            if inverse.is_zero() {
+                return Err(InvalidRowOperationError);
+            }
+            if i != pivot_row {
+                self.apply(RowOperation::Swap(pivot_row, i))?;
+            }
+            if !Coefficient::is_one(&pivot) {
+                self.apply(RowOperation::Scale(pivot_row, inverse))?;
+            }
+            for i in (0..nrows).filter(|&i| i != pivot_row) {
+                let entry = self.current()[(i, j)].clone();
+                if !entry.is_zero() {
+                    self.apply(RowOperation::AddScaled {
+                        target: i,
+                        source: pivot_row,
+                        factor: -entry,
+                    })?;
+                }
+            }
+            pivot_row += 1;
+        }
+        Ok(())
+    }
+}
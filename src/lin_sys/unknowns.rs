@@ -1,9 +1,10 @@
-use core::{fmt::Error, marker::PhantomData, any::type_name};
+use core::{marker::PhantomData, any::type_name};
 
 use nalgebra::Dim;
 
 use super::err::OutOfBoundsError;
 use crate::{
+    error::Error,
     latex_modes::MathLatexMode,
     latex_writer::LatexWriter,
     lin_sys::numbering::{Numbering, NumberingTy},
@@ -21,6 +22,9 @@ pub trait Unknowns {
         M: MathLatexMode,
         W: LatexWriter<Mode = M>;
 
+    /// # Safety
+    ///
+    /// The zero-based index `zbi` must be [valid](Unknowns::validate_idx).
     unsafe fn write_ith_unchecked<W, M>(&self, w: &mut W, zbi: usize) -> Result<(), Error>
     where
         M: MathLatexMode,
@@ -31,8 +35,14 @@ pub trait Unknowns {
         M: MathLatexMode,
         W: LatexWriter<Mode = M>,
     {
-        self.validate_idx(zbi).map_err(|_| Error)?;
-        unsafe { self.write_ith_unchecked(w, zbi) }
+        if self.validate_idx(zbi).is_err() {
+            return Err(Error::IndexOutOfBounds {
+                index: zbi,
+                len: self.len().unwrap_or(0),
+            });
+        }
+        unsafe { self.write_ith_unchecked(w, zbi) }?;
+        Ok(())
     }
 }
 
@@ -69,10 +79,9 @@ impl<const N: usize> SingleLetterBoldfaceVecOfDistinctSingleLetterUnknowns<N> {
     pub fn new(c: char, unknowns: [char; N]) -> Self {
         #[cfg(debug_assertions)]
         {
-            let mut hs = std::collections::HashSet::<char>::with_capacity(N);
-            for c in unknowns {
-                let is_newly_inserted = hs.insert(c);
-                if !is_newly_inserted {
+            // Quadratic but allocation-free, and the unknowns are few
+            for (i, c) in unknowns.iter().enumerate() {
+                if unknowns[..i].contains(c) {
                     panic!("Unexpected repetitions. Unknowns in {type_name} should be distinct.",
                         type_name = type_name::<Self>()
                     )
@@ -100,7 +109,7 @@ where
         Ok(self.len.value())
     }
 
-    fn write<W, M>(&self, w: &mut W) -> Result<(), core::fmt::Error>
+    fn write<W, M>(&self, w: &mut W) -> Result<(), Error>
     where
         M: MathLatexMode,
         W: LatexWriter<Mode = M>,
@@ -108,8 +117,9 @@ where
         unsafe {
             w.write_str(r"\textbf{")?;
             w.write_char(self.c)?;
-            w.write_str(r"}")
+            w.write_str(r"}")?;
         }
+        Ok(())
     }
 
     fn validate_idx(&self, zbi: usize) -> Result<(), OutOfBoundsError> {
@@ -137,7 +147,8 @@ where
                 #[cfg_attr(feature = "adt_const_params", allow(unreachable_patterns))]
                 _ => panic!("unsupported numbering"),
             }
-        ))
+        ))?;
+        Ok(())
     }
 }
 
@@ -150,7 +161,7 @@ impl<const N: usize> Unknowns for SingleLetterBoldfaceVecOfDistinctSingleLetterU
         Ok(self.unknowns.len())
     }
     
-    fn write<W, M>(&self, w: &mut W) -> Result<(), core::fmt::Error>
+    fn write<W, M>(&self, w: &mut W) -> Result<(), Error>
     where
         M: MathLatexMode,
         W: LatexWriter<Mode = M>,
@@ -158,8 +169,9 @@ impl<const N: usize> Unknowns for SingleLetterBoldfaceVecOfDistinctSingleLetterU
         unsafe {
             w.write_str(r"\textbf{")?;
             w.write_char(self.c)?;
-            w.write_str(r"}")
+            w.write_str(r"}")?;
         }
+        Ok(())
     }
 
     fn validate_idx(&self, zbi: usize) -> Result<(), OutOfBoundsError> {
@@ -175,6 +187,7 @@ impl<const N: usize> Unknowns for SingleLetterBoldfaceVecOfDistinctSingleLetterU
             M: MathLatexMode,
             W: LatexWriter<Mode = M> {
         let unknown_ref = self.unknowns.get_unchecked(zbi);
-        w.write_char(*unknown_ref)
+        w.write_char(*unknown_ref)?;
+        Ok(())
     }
 }
\ No newline at end of file
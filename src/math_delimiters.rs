@@ -0,0 +1,149 @@
+//! Module with delimiters of math blocks, e.g. `\(` and `\)`.
+//!
+//! `$` and `$$` are the delimiters of plain TeX. In LaTeX2e, `$$` is discouraged because it
+//! bypasses the LaTeX code that handles display math, which breaks e.g. `fleqn` and the
+//! vertical spacing of [`amsmath`][amsmath]. The delimiters are available only for the
+//! flavors that support them, see the implementors of [`MathDelimiters`].
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::{DisplayMathMode, InlineMathMode, InnerParagraphMode},
+//!     latex_writer::{LatexWriter, Writer},
+//!     math_delimiters::{BackslashBrackets, BackslashParentheses, EquationStar},
+//!     text::{RawLatex, WriteRawLatex},
+//! };
+//!
+//! let w = Writer::<AmsLatex, NoFeatures, InnerParagraphMode, String>::default();
+//! let mut w: Writer<_, _, InlineMathMode, _> = w.open_math::<BackslashParentheses>().unwrap();
+//! w.write_raw(RawLatex::new("x").unwrap()).unwrap();
+//! // Safe since the math was opened from `InnerParagraphMode`
+//! let w = unsafe { w.close_math::<BackslashParentheses, InnerParagraphMode>() }
+//!     .unwrap()
+//!     .write_math::<BackslashBrackets, _>(|mut w| {
+//!         w.write_raw(RawLatex::new("y").unwrap())?;
+//!         Ok(w)
+//!     })
+//!     .unwrap()
+//!     .write_math::<EquationStar, _>(|mut w: Writer<_, _, DisplayMathMode, _>| {
+//!         w.write_raw(RawLatex::new("z").unwrap())?;
+//!         Ok(w)
+//!     })
+//!     .unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"\(x\)\[y\]\begin{equation*}z\end{equation*}");
+//! ```
+//!
+//! The delimiters can also be written with [`latex_format!`](crate::latex_format):
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     latex_features::NoFeatures,
+//!     latex_flavors::MathJax,
+//!     latex_format,
+//!     latex_modes::InnerParagraphMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! let w = Writer::<MathJax, NoFeatures, InnerParagraphMode, String>::default();
+//! latex_format!(
+//!     #[on_format_error(unwrap)]
+//!     w += "Let " ; "\\(" ; "x=1" ; "\\)" ; ", then" ;
+//!     "\\begin{equation*}" ; "x^{2}=1" ; "\\end{equation*}" ;
+//! );
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"Let \(x=1\), then\begin{equation*}x^{2}=1\end{equation*}");
+//! ```
+//!
+//! The writer returns to the mode the math was opened from, e.g. to [`OuterParagraphMode`]:
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_format,
+//!     latex_modes::OuterParagraphMode,
+//!     latex_writer::{LatexWriter, Writer},
+//! };
+//!
+//! let w = Writer::<AmsLatex, NoFeatures, OuterParagraphMode, String>::default();
+//! latex_format!(#[on_format_error(unwrap)] w += "\\[" ; "x" ; "\\]" ;);
+//! let w: Writer<_, _, OuterParagraphMode, _> = w;
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"\[x\]");
+//! ```
+//!
+//! [`OuterParagraphMode`]: crate::latex_modes::OuterParagraphMode
+//!
+//! [amsmath]: https://ctan.org/pkg/amsmath
+
+use crate::{
+    latex_features::Package,
+    latex_flavors::{
+        AmsLatex, AmsTex, Latex209Plus, LatexFlavorKindExt, Luatex, MathJax, PlainTex, Xelatex,
+    },
+    latex_modes::{DisplayMathMode, InlineMathMode, MathLatexMode},
+};
+
+/// Implementors are delimiters of math blocks that are supported by the flavor `Fl`.
+///
+/// # Safety
+///
+/// [`OPENING`] must make [LaTeX] enter [`Mode`] and [`CLOSING`] must make it leave the mode.
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+/// [`OPENING`]: MathDelimiters::OPENING
+/// [`CLOSING`]: MathDelimiters::CLOSING
+/// [`Mode`]: MathDelimiters::Mode
+pub unsafe trait MathDelimiters<Fl: LatexFlavorKindExt> {
+    type Mode: MathLatexMode;
+
+    const OPENING: &'static str;
+    const CLOSING: &'static str;
+    /// The package that provides the delimiters, if any
+    const PACKAGE: Option<Package> = None;
+}
+
+macro_rules! decl_math_delimiters {
+    ($(
+        $(#[$attr:meta])*
+        $delims:ident($mode:ident, $opening:literal, $closing:literal, $package:expr)
+            for $($flavor:ident),+;
+    )+) => {
+        $(
+            $(#[$attr])*
+            pub struct $delims;
+
+            $(
+                unsafe impl MathDelimiters<$flavor> for $delims {
+                    type Mode = $mode;
+
+                    const OPENING: &'static str = $opening;
+                    const CLOSING: &'static str = $closing;
+                    const PACKAGE: Option<Package> = $package;
+                }
+            )+
+        )+
+    };
+}
+
+decl_math_delimiters! {
+    /// `$` and `$`, i.e. the delimiters of inline math in plain TeX
+    DollarSign(InlineMathMode, "$", "$", None)
+        for PlainTex, AmsTex, AmsLatex, Latex209Plus, MathJax, Luatex, Xelatex;
+    /// `$$` and `$$`, i.e. the delimiters of display math in plain TeX
+    TwoDollarSigns(DisplayMathMode, "$$", "$$", None)
+        for PlainTex, AmsTex, AmsLatex, Latex209Plus, MathJax, Luatex, Xelatex;
+    /// `\(` and `\)`, i.e. the delimiters of inline math in LaTeX
+    BackslashParentheses(InlineMathMode, r"\(", r"\)", None)
+        for AmsLatex, Latex209Plus, MathJax, Luatex, Xelatex;
+    /// `\[` and `\]`, i.e. the delimiters of display math in LaTeX
+    BackslashBrackets(DisplayMathMode, r"\[", r"\]", None)
+        for AmsLatex, Latex209Plus, MathJax, Luatex, Xelatex;
+    /// `equation*` environment, i.e. unnumbered display math of [`amsmath`](https://ctan.org/pkg/amsmath)
+    EquationStar(DisplayMathMode, r"\begin{equation*}", r"\end{equation*}", Some(Package::Amsmath))
+        for AmsLatex, MathJax, Luatex, Xelatex;
+}
@@ -0,0 +1,40 @@
+//! Module with delimiters that can be put around matrix-like [LaTeX] constructs,
+//! such as `array` environments, using `\left` and `\right`.
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+
+pub trait Delimiters {
+    /// The opening delimiter, e.g. `\left(`
+    const LEFT: &'static str;
+    /// The closing delimiter, e.g. `\right)`
+    const RIGHT: &'static str;
+}
+
+macro_rules! decl_delimiters {
+    ($($(#[$attr:meta])* $delims:ident => ($left:literal, $right:literal),)+) => {
+        $(
+            $(#[$attr])*
+            pub struct $delims;
+
+            impl Delimiters for $delims {
+                const LEFT: &'static str = $left;
+                const RIGHT: &'static str = $right;
+            }
+        )+
+    };
+}
+
+decl_delimiters! {
+    /// No delimiters at all
+    NoDelimiters => ("", ""),
+    /// `(` and `)`
+    Parentheses => (r"\left(", r"\right)"),
+    /// `[` and `]`
+    Brackets => (r"\left[", r"\right]"),
+    /// `{` and `}`
+    Braces => (r"\left\{", r"\right\}"),
+    /// `|` and `|`
+    VBars => (r"\left|", r"\right|"),
+    /// `‖` and `‖`
+    DoubleVBars => (r"\left\|", r"\right\|"),
+}
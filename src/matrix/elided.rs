@@ -0,0 +1,116 @@
+//! Module with a wrapper for eliding the middle rows and columns of large matrices.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use nalgebra::DMatrix;
+//! use nalgebra_latex::{
+//!     fmt::LatexFormatter,
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::DisplayMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//!     matrix::{elided::ElidedMatrix, fmt::BracketedMatrixFormatter},
+//! };
+//!
+//! type W = Writer<AmsLatex, NoFeatures, DisplayMathMode, String>;
+//!
+//! let m = DMatrix::from_fn(500, 500, |i, j| i * 500 + j);
+//! let elided = ElidedMatrix::new(&m, 1, 1, 2, 1);
+//! let w: W = BracketedMatrixFormatter::fmt(W::default(), &elided).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     concat!(
+//!         r"\begin{bmatrix}",
+//!         r"0&1&\cdots&499\\",
+//!         r"\vdots&\vdots&\ddots&\vdots\\",
+//!         r"249500&249501&\cdots&249999",
+//!         r"\end{bmatrix}",
+//!     )
+//! );
+//!
+//! // Rows are not elided because the head and the tail cover all of them
+//! let m = DMatrix::from_fn(2, 5, |i, j| i + j);
+//! let elided = ElidedMatrix::new(&m, 1, 1, 1, 1);
+//! let w: W = BracketedMatrixFormatter::fmt(W::default(), &elided).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"\begin{bmatrix}0&\cdots&4\\1&\cdots&5\end{bmatrix}");
+//! # }
+//! ```
+
+use nalgebra::{Dim, Matrix, RawStorage};
+
+/// A view of a [`nalgebra::Matrix`] where only the first and the last rows and columns are
+/// shown and the rest are replaced with `\cdots`, `\vdots`, and `\ddots`.
+///
+/// The wrapper is accepted as input by every formatter in [`matrix::fmt`][super::fmt].
+pub struct ElidedMatrix<'a, T, R, C, S>
+where
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    /// The matrix to be shown
+    pub matrix: &'a Matrix<T, R, C, S>,
+    /// The number of the first rows that are shown
+    pub head_rows: usize,
+    /// The number of the last rows that are shown
+    pub tail_rows: usize,
+    /// The number of the first columns that are shown
+    pub head_cols: usize,
+    /// The number of the last columns that are shown
+    pub tail_cols: usize,
+}
+
+/// The slot of a row or a column of an [`ElidedMatrix`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Slot {
+    /// The row or the column with the given zero-based index
+    Index(usize),
+    /// The placeholder for the elided rows or columns
+    Ellipsis,
+}
+
+impl<'a, T, R, C, S> ElidedMatrix<'a, T, R, C, S>
+where
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    pub fn new(
+        matrix: &'a Matrix<T, R, C, S>,
+        head_rows: usize,
+        tail_rows: usize,
+        head_cols: usize,
+        tail_cols: usize,
+    ) -> Self {
+        Self {
+            matrix,
+            head_rows,
+            tail_rows,
+            head_cols,
+            tail_cols,
+        }
+    }
+
+    pub(crate) fn row_slots(&self) -> impl Iterator<Item = Slot> + Clone {
+        slots(self.matrix.nrows(), self.head_rows, self.tail_rows)
+    }
+
+    pub(crate) fn col_slots(&self) -> impl Iterator<Item = Slot> + Clone {
+        slots(self.matrix.ncols(), self.head_cols, self.tail_cols)
+    }
+}
+
+fn slots(len: usize, head: usize, tail: usize) -> impl Iterator<Item = Slot> + Clone {
+    // Eliding a single row or column would not make the output shorter
+    let is_elided = head.saturating_add(tail).saturating_add(1) < len;
+    let (head, tail) = if is_elided { (head, tail) } else { (len, 0) };
+    (0..head)
+        .map(Slot::Index)
+        .chain(is_elided.then_some(Slot::Ellipsis))
+        .chain((len - tail..len).map(Slot::Index))
+}
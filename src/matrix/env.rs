@@ -0,0 +1,50 @@
+//! Module with [`amsmath` matrix environments][matrices].
+//!
+//! [matrices]: https://www.overleaf.com/learn/latex/Matrices
+
+use crate::{
+    env::LatexEnvironment,
+    latex_features::{LatexFeatures, Package},
+    latex_flavors::LatexFlavor,
+    latex_modes::MathLatexMode,
+    latex_writer::LatexWriter,
+};
+
+macro_rules! decl_matrix_environments {
+    ($($(#[$attr:meta])* $env:ident => $name:literal,)+) => {
+        $(
+            $(#[$attr])*
+            pub struct $env;
+
+            unsafe impl<Fl, Fe, M, W, InitW> LatexEnvironment<Fl, Fe, M, W, InitW> for $env
+            where
+                Fl: LatexFlavor,
+                Fe: LatexFeatures,
+                M: MathLatexMode,
+                W: core::fmt::Write,
+                InitW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M, NestedWriter = W>,
+            {
+                type InnerMode = M;
+                const PACKAGE: Option<Package> = Some(Package::Amsmath);
+                fn write_name(w: &mut W) -> Result<(), core::fmt::Error> {
+                    w.write_str($name)
+                }
+            }
+        )+
+    };
+}
+
+decl_matrix_environments! {
+    /// `matrix` environment, i.e. a matrix without delimiters
+    PlainMatrixEnvironment => "matrix",
+    /// `pmatrix` environment, i.e. a matrix delimited with `(` and `)`
+    ParenthesizedMatrixEnvironment => "pmatrix",
+    /// `bmatrix` environment, i.e. a matrix delimited with `[` and `]`
+    BracketedMatrixEnvironment => "bmatrix",
+    /// `Bmatrix` environment, i.e. a matrix delimited with `{` and `}`
+    BracedMatrixEnvironment => "Bmatrix",
+    /// `vmatrix` environment, i.e. a matrix delimited with `|` and `|`
+    VBarDelimitedMatrixEnvironment => "vmatrix",
+    /// `Vmatrix` environment, i.e. a matrix delimited with `‖` and `‖`
+    DoubleVBarDelimitedMatrixEnvironment => "Vmatrix",
+}
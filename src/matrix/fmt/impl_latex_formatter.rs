@@ -0,0 +1,90 @@
+use nalgebra::{Dim, Matrix, RawStorage};
+
+use crate::{
+    env::LatexEnvironment,
+    fmt::{LatexFormatter, PartialEndofunctionalWriteAsLatex},
+    latex_features::LatexFeatures,
+    latex_flavors::LatexFlavor,
+    latex_modes::MathLatexMode,
+    latex_writer::LatexWriter,
+    matrix::elided::ElidedMatrix,
+    matrix::env::{
+        BracedMatrixEnvironment, BracketedMatrixEnvironment, DoubleVBarDelimitedMatrixEnvironment,
+        ParenthesizedMatrixEnvironment, PlainMatrixEnvironment, VBarDelimitedMatrixEnvironment,
+    },
+};
+
+use super::{
+    write_elided_matrix_body, write_matrix_body, BracedMatrixFormatter, BracketedMatrixFormatter,
+    DoubleVBarDelimitedMatrixFormatter, ParenthesizedMatrixFormatter, PlainMatrixFormatter,
+    VBarDelimitedMatrixFormatter,
+};
+
+macro_rules! impl_for_matrix_env {
+    ($formatter:ident, $env:ident) => {
+        impl<Fl, Fe, M, T, R, C, S> LatexFormatter<Fl, Fe, Fe, M, M, Matrix<T, R, C, S>>
+            for $formatter
+        where
+            Fl: LatexFlavor,
+            Fe: LatexFeatures,
+            M: MathLatexMode,
+            T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+            R: Dim,
+            C: Dim,
+            S: RawStorage<T, R, C>,
+        {
+            fn fmt<IW, OW>(dest: IW, input: &Matrix<T, R, C, S>) -> Result<OW, crate::error::Error>
+            where
+                IW: LatexWriter<
+                    Flavor = Fl,
+                    Features = Fe,
+                    Mode = M,
+                    NestedWriter = OW::NestedWriter,
+                >,
+                OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+            {
+                let dest: IW = $env::with_env(dest, |dest| write_matrix_body(dest, input))?;
+                Ok(unsafe { dest.rebuild() })
+            }
+        }
+
+        impl<'a, Fl, Fe, M, T, R, C, S>
+            LatexFormatter<Fl, Fe, Fe, M, M, ElidedMatrix<'a, T, R, C, S>> for $formatter
+        where
+            Fl: LatexFlavor,
+            Fe: LatexFeatures,
+            M: MathLatexMode,
+            T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+            R: Dim,
+            C: Dim,
+            S: RawStorage<T, R, C>,
+        {
+            fn fmt<IW, OW>(
+                dest: IW,
+                input: &ElidedMatrix<'a, T, R, C, S>,
+            ) -> Result<OW, crate::error::Error>
+            where
+                IW: LatexWriter<
+                    Flavor = Fl,
+                    Features = Fe,
+                    Mode = M,
+                    NestedWriter = OW::NestedWriter,
+                >,
+                OW: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+            {
+                let dest: IW = $env::with_env(dest, |dest| write_elided_matrix_body(dest, input))?;
+                Ok(unsafe { dest.rebuild() })
+            }
+        }
+    };
+}
+
+impl_for_matrix_env!(PlainMatrixFormatter, PlainMatrixEnvironment);
+impl_for_matrix_env!(ParenthesizedMatrixFormatter, ParenthesizedMatrixEnvironment);
+impl_for_matrix_env!(BracketedMatrixFormatter, BracketedMatrixEnvironment);
+impl_for_matrix_env!(BracedMatrixFormatter, BracedMatrixEnvironment);
+impl_for_matrix_env!(VBarDelimitedMatrixFormatter, VBarDelimitedMatrixEnvironment);
+impl_for_matrix_env!(
+    DoubleVBarDelimitedMatrixFormatter,
+    DoubleVBarDelimitedMatrixEnvironment
+);
@@ -0,0 +1,165 @@
+//! A module offering a number of [LaTeX] formatters for [`nalgebra::Matrix`].
+//!
+//! Every formatter corresponds to one of the [`amsmath` matrix environments][matrices]
+//! from [`matrix::env`][super::env] and accepts both [`nalgebra::Matrix`] and
+//! [`ElidedMatrix`] as input.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra::{matrix, vector};
+//! use nalgebra_latex::{
+//!     fmt::LatexFormatter,
+//!     latex_features::NoFeatures,
+//!     latex_flavors::AmsLatex,
+//!     latex_modes::DisplayMathMode,
+//!     latex_writer::{LatexWriter, Writer},
+//!     matrix::fmt::{
+//!         BracedMatrixFormatter, DoubleVBarDelimitedMatrixFormatter, PlainMatrixFormatter,
+//!         VBarDelimitedMatrixFormatter,
+//!     },
+//! };
+//!
+//! type W = Writer<AmsLatex, NoFeatures, DisplayMathMode, String>;
+//!
+//! let v = vector![1u8, 2];
+//! let w: W = PlainMatrixFormatter::fmt(W::default(), &v).unwrap();
+//! let w: W = BracedMatrixFormatter::fmt(w, &v).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{matrix}1\\2\end{matrix}\begin{Bmatrix}1\\2\end{Bmatrix}"
+//! );
+//!
+//! let m = matrix![1i32, -2; 3, 4];
+//! let w: W = VBarDelimitedMatrixFormatter::fmt(W::default(), &m).unwrap();
+//! let w: W = DoubleVBarDelimitedMatrixFormatter::fmt(w, &m.transpose()).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"\begin{vmatrix}1&-2\\3&4\end{vmatrix}\begin{Vmatrix}1&3\\-2&4\end{Vmatrix}"
+//! );
+//! ```
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+//! [matrices]: https://www.overleaf.com/learn/latex/Matrices
+
+use nalgebra::{Dim, Matrix, RawStorage};
+
+use super::elided::{ElidedMatrix, Slot};
+use crate::{
+    fmt::PartialEndofunctionalWriteAsLatex, latex_features::LatexFeatures,
+    latex_flavors::LatexFlavor, latex_modes::MathLatexMode, latex_writer::LatexWriter,
+};
+
+mod impl_latex_formatter;
+
+/// Formatter for the `matrix` environment
+pub struct PlainMatrixFormatter;
+
+/// Formatter for the `pmatrix` environment
+pub struct ParenthesizedMatrixFormatter;
+
+/// Formatter for the `bmatrix` environment
+pub struct BracketedMatrixFormatter;
+
+/// Formatter for the `Bmatrix` environment
+pub struct BracedMatrixFormatter;
+
+/// Formatter for the `vmatrix` environment
+pub struct VBarDelimitedMatrixFormatter;
+
+/// Formatter for the `Vmatrix` environment
+pub struct DoubleVBarDelimitedMatrixFormatter;
+
+// Writes the cells of the matrix separated by `&` and the rows separated by `\\`,
+// which is the body shared by all matrix-like environments.
+pub(crate) fn write_matrix_body<Fl, Fe, M, T, R, C, S, W>(
+    dest: W,
+    input: &Matrix<T, R, C, S>,
+) -> Result<W, crate::error::Error>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+{
+    let (nrows, ncols) = input.shape();
+    write_slotted_matrix_body(
+        dest,
+        input,
+        (0..nrows).map(Slot::Index),
+        (0..ncols).map(Slot::Index),
+    )
+}
+
+// Same as `write_matrix_body` but the elided rows and columns are replaced with
+// `\vdots`, `\cdots`, and `\ddots`.
+pub(crate) fn write_elided_matrix_body<Fl, Fe, M, T, R, C, S, W>(
+    dest: W,
+    input: &ElidedMatrix<T, R, C, S>,
+) -> Result<W, crate::error::Error>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+{
+    write_slotted_matrix_body(dest, input.matrix, input.row_slots(), input.col_slots())
+}
+
+fn write_slotted_matrix_body<Fl, Fe, M, T, R, C, S, W, RI, CI>(
+    mut dest: W,
+    input: &Matrix<T, R, C, S>,
+    row_slots: RI,
+    col_slots: CI,
+) -> Result<W, crate::error::Error>
+where
+    Fl: LatexFlavor,
+    Fe: LatexFeatures,
+    M: MathLatexMode,
+    T: PartialEndofunctionalWriteAsLatex<Fl, Fe, M>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+    W: LatexWriter<Flavor = Fl, Features = Fe, Mode = M>,
+    RI: Iterator<Item = Slot>,
+    CI: Iterator<Item = Slot> + Clone,
+{
+    for (i, row_slot) in row_slots.enumerate() {
+        if i != 0 {
+            unsafe { dest.write_str(r"\\") }?;
+        }
+        for (j, col_slot) in col_slots.clone().enumerate() {
+            if j != 0 {
+                unsafe { dest.write_char('&') }?;
+            }
+            dest = match (row_slot, col_slot) {
+                (Slot::Index(i), Slot::Index(j)) => {
+                    input[(i, j)].partial_endofunctional_write_as_latex(dest)?
+                }
+                (Slot::Index(_), Slot::Ellipsis) => {
+                    unsafe { dest.write_str(r"\cdots") }?;
+                    dest
+                }
+                (Slot::Ellipsis, Slot::Index(_)) => {
+                    unsafe { dest.write_str(r"\vdots") }?;
+                    dest
+                }
+                (Slot::Ellipsis, Slot::Ellipsis) => {
+                    unsafe { dest.write_str(r"\ddots") }?;
+                    dest
+                }
+            };
+        }
+    }
+    Ok(dest)
+}
@@ -0,0 +1,41 @@
+//! A module offering [LaTeX] formatters and environments for [`nalgebra::Matrix`].
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra::matrix;
+//! use nalgebra_latex::{
+//!     fmt::LatexFormatter,
+//!     latex_features::NoFeatures,
+//!     latex_flavors::{AmsLatex, MathJax},
+//!     latex_modes::{DisplayMathMode, InlineMathMode},
+//!     latex_writer::{LatexWriter, Writer},
+//!     matrix::fmt::{BracketedMatrixFormatter, ParenthesizedMatrixFormatter},
+//! };
+//!
+//! let m = matrix![1, 2, 3; 4, 5, 6];
+//! let w = Writer::<AmsLatex, NoFeatures, InlineMathMode, String>::default();
+//! let w: Writer<AmsLatex, NoFeatures, InlineMathMode, String> =
+//!     ParenthesizedMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"\begin{pmatrix}1&2&3\\4&5&6\end{pmatrix}");
+//!
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use nalgebra::DMatrix;
+//!
+//! let m = DMatrix::from_row_slice(2, 2, &[1.5, -2.0, 0.25, 4.0]);
+//! let w = Writer::<MathJax, NoFeatures, DisplayMathMode, String>::default();
+//! let w: Writer<MathJax, NoFeatures, DisplayMathMode, String> =
+//!     BracketedMatrixFormatter::fmt(w, &m).unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"\begin{bmatrix}1.5&-2\\0.25&4\end{bmatrix}");
+//! # }
+//! ```
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+
+pub mod delimiters;
+pub mod elided;
+pub mod env;
+pub mod fmt;
@@ -0,0 +1,266 @@
+//! Module with safe writing of arbitrary strings, e.g. user-supplied ones.
+//!
+//! Writing with [`UnsafeWrite`] can break the generated [LaTeX] code. The module offers
+//! two safe alternatives:
+//!
+//! * [`WriteText::write_text`] writes a string as text. In horizontal modes, the ten
+//!   special characters (`# $ % & ~ _ ^ \ { }`) are escaped. In math modes, the text is
+//!   wrapped in `\text{}` and the escaping depends on the flavor: [MathJax] does not
+//!   interpret macros in `\text{}` so the characters that can still break the code are
+//!   written outside of it, as math symbols.
+//! * [`WriteRawLatex::write_raw`] writes [`RawLatex`], i.e. [LaTeX] code that has been
+//!   checked to have balanced braces and neither unescaped `%`, which would comment out
+//!   the code written after it, nor unescaped `$`, which would switch the mode behind the
+//!   writer's back. The check is lexical: commands that break the code otherwise,
+//!   e.g. `\end{document}`, are not detected.
+//!
+//! # Example
+//!
+//! ```
+//! use nalgebra_latex::{
+//!     latex_features::NoFeatures,
+//!     latex_flavors::{AmsLatex, MathJax},
+//!     latex_modes::{InlineMathMode, InnerParagraphMode},
+//!     latex_writer::{LatexWriter, Writer},
+//!     text::{RawLatex, WriteRawLatex, WriteText},
+//! };
+//!
+//! let mut w = Writer::<AmsLatex, NoFeatures, InnerParagraphMode, String>::default();
+//! w.write_text(r"50% of {a_1 & a_2} cost $5 \o/").unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(
+//!     s,
+//!     r"50\% of \{a\_1 \& a\_2\} cost \$5 \textbackslash{}o/"
+//! );
+//!
+//! let mut w = Writer::<AmsLatex, NoFeatures, InlineMathMode, String>::default();
+//! w.write_raw(RawLatex::new(r"x_{1}=").unwrap()).unwrap();
+//! w.write_text("#1 & ~2").unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"x_{1}=\text{\#1 \& \textasciitilde{}2}");
+//!
+//! let mut w = Writer::<MathJax, NoFeatures, InlineMathMode, String>::default();
+//! w.write_text(r"{x} in \R").unwrap();
+//! let (s, _no_features) = w.into_raw_parts();
+//! assert_eq!(s, r"\{\text{x}\}\text{ in }\backslash\text{R}");
+//!
+//! assert!(RawLatex::new(r"\frac{1}{2").is_err());
+//! assert!(RawLatex::new(r"\}\frac{1}{2}\{").is_ok());
+//! assert!(RawLatex::new(r"x\").is_err());
+//! assert!(RawLatex::new(r"50% of x").is_err());
+//! assert!(RawLatex::new(r"50\% of $x$").is_err());
+//! assert!(RawLatex::new(r"\$5").is_ok());
+//! ```
+//!
+//! [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+//! [MathJax]: https://www.mathjax.org/
+
+use core::fmt::{Error, Write};
+
+use crate::{
+    latex_features::{LatexFeatures, Package},
+    latex_flavors::{LatexFlavorKind, LatexFlavorKindExt},
+    latex_modes::{
+        DisplayMathMode, InlineMathMode, InnerParagraphMode, LRMode, OuterParagraphMode,
+    },
+    latex_writer::{LatexWriter, UnsafeWrite, Writer},
+};
+
+/// The error returned by [`RawLatex::new`] when the string has unbalanced braces,
+/// an unescaped `%` or `$`, or ends with a backslash that would escape the character
+/// written after it.
+#[derive(Debug)]
+pub struct InvalidRawLatexError;
+
+/// [LaTeX] code with balanced braces and without unescaped `%` and `$` or a dangling backslash.
+///
+/// [LaTeX]: https://www.overleaf.com/learn/latex/Learn_LaTeX_in_30_minutes#What_is_LaTeX.3F
+#[derive(Clone, Copy)]
+pub struct RawLatex<'a>(&'a str);
+
+impl<'a> RawLatex<'a> {
+    pub fn new(s: &'a str) -> Result<Self, InvalidRawLatexError> {
+        let mut depth = 0usize;
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                // The guard skips the escaped character, e.g. `{` in `\{`, which is not a group delimiter.
+                '\\' if chars.next().is_none() => return Err(InvalidRawLatexError),
+                '{' => depth += 1,
+                '}' => depth = depth.checked_sub(1).ok_or(InvalidRawLatexError)?,
+                // `%` comments out the rest of the line and `$` enters or leaves math mode
+                '%' | '$' => return Err(InvalidRawLatexError),
+                _ => {}
+            }
+        }
+        if depth == 0 {
+            Ok(Self(s))
+        } else {
+            Err(InvalidRawLatexError)
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+/// Writes the string escaping the special characters for text (i.e. horizontal) modes.
+pub fn write_escaped_text<W: Write>(dest: &mut W, s: &str) -> Result<(), Error> {
+    for c in s.chars() {
+        match c {
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                dest.write_char('\\')?;
+                dest.write_char(c)?;
+            }
+            '~' => dest.write_str(r"\textasciitilde{}")?,
+            '^' => dest.write_str(r"\textasciicircum{}")?,
+            '\\' => dest.write_str(r"\textbackslash{}")?,
+            c => dest.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+/// Writes the string as `\text{}` in math mode using the escaping policy of the flavor.
+///
+/// [`MathJax`] and [`Texvc`] do not interpret macros inside of `\text{}`, so escaping
+/// there is impossible. Instead, braces, backslashes, dollar and percent signs are
+/// written between `\text{}` blocks as math symbols. The other flavors escape the text
+/// the same way as [`write_escaped_text`] does.
+///
+/// [`MathJax`]: crate::latex_flavors::MathJax
+/// [`Texvc`]: crate::latex_flavors::Texvc
+pub fn write_escaped_math_text<Fl, W>(dest: &mut W, s: &str) -> Result<(), Error>
+where
+    Fl: LatexFlavorKindExt,
+    W: Write,
+{
+    write_escaped_math_text_in::<Fl, W>(dest, r"\text", s)
+}
+
+// Same as `write_escaped_math_text` but with another text command, e.g. `\textit`.
+pub(crate) fn write_escaped_math_text_in<Fl, W>(
+    dest: &mut W,
+    command: &str,
+    s: &str,
+) -> Result<(), Error>
+where
+    Fl: LatexFlavorKindExt,
+    W: Write,
+{
+    if !matches!(Fl::KIND, LatexFlavorKind::MathJax | LatexFlavorKind::Texvc) {
+        dest.write_str(command)?;
+        dest.write_char('{')?;
+        write_escaped_text(dest, s)?;
+        return dest.write_char('}');
+    }
+    let mut is_text_open = false;
+    for c in s.chars() {
+        let symbol = match c {
+            '{' => r"\{",
+            '}' => r"\}",
+            '\\' => r"\backslash",
+            '$' => r"\$",
+            '%' => r"\%",
+            c => {
+                if !is_text_open {
+                    dest.write_str(command)?;
+                    dest.write_char('{')?;
+                    is_text_open = true;
+                }
+                dest.write_char(c)?;
+                continue;
+            }
+        };
+        if is_text_open {
+            dest.write_char('}')?;
+            is_text_open = false;
+        }
+        dest.write_str(symbol)?;
+    }
+    if is_text_open {
+        dest.write_char('}')?;
+    }
+    Ok(())
+}
+
+/// Implementors can safely write arbitrary strings as text.
+///
+/// In math modes, writing fails with [`Error::UnsupportedByFlavor`] for [`PlainTex`],
+/// which has no `\text` command.
+///
+/// [`Error::UnsupportedByFlavor`]: crate::error::Error::UnsupportedByFlavor
+/// [`PlainTex`]: crate::latex_flavors::PlainTex
+pub trait WriteText: LatexWriter {
+    fn write_text(&mut self, s: &str) -> Result<(), crate::error::Error>;
+}
+
+/// Implementors can safely write [`RawLatex`].
+pub trait WriteRawLatex: LatexWriter {
+    fn write_raw(&mut self, raw: RawLatex) -> Result<(), crate::error::Error>;
+}
+
+impl<T: LatexWriter> WriteRawLatex for T {
+    fn write_raw(&mut self, raw: RawLatex) -> Result<(), crate::error::Error> {
+        unsafe { self.write_str(raw.as_str()) }.map_err(crate::error::Error::from)
+    }
+}
+
+struct UnsafeWriteAdapter<'a, W: ?Sized>(&'a mut W);
+
+impl<'a, W: UnsafeWrite + ?Sized> Write for UnsafeWriteAdapter<'a, W> {
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        // The callers write only escaped strings
+        unsafe { self.0.write_str(s) }
+    }
+
+    fn write_char(&mut self, c: char) -> Result<(), Error> {
+        unsafe { self.0.write_char(c) }
+    }
+}
+
+macro_rules! impl_write_text_for_text_modes {
+    ($($mode:ident),+) => {
+        $(
+            impl<Fl, Fe, W> WriteText for Writer<Fl, Fe, $mode, W>
+            where
+                Fl: LatexFlavorKindExt,
+                Fe: LatexFeatures,
+                W: Write,
+            {
+                fn write_text(&mut self, s: &str) -> Result<(), crate::error::Error> {
+                    write_escaped_text(&mut UnsafeWriteAdapter(self), s)?;
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_write_text_for_math_modes {
+    ($($mode:ident),+) => {
+        $(
+            impl<Fl, Fe, W> WriteText for Writer<Fl, Fe, $mode, W>
+            where
+                Fl: LatexFlavorKindExt,
+                Fe: LatexFeatures,
+                W: Write,
+            {
+                fn write_text(&mut self, s: &str) -> Result<(), crate::error::Error> {
+                    if let LatexFlavorKind::PlainTex = Fl::KIND {
+                        return Err(crate::error::Error::UnsupportedByFlavor {
+                            construct: r"\text",
+                        });
+                    }
+                    self.require_package(Package::Amsmath);
+                    write_escaped_math_text::<Fl, _>(&mut UnsafeWriteAdapter(self), s)?;
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+impl_write_text_for_text_modes!(OuterParagraphMode, InnerParagraphMode, LRMode);
+impl_write_text_for_math_modes!(InlineMathMode, DisplayMathMode);